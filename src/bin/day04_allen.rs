@@ -0,0 +1,170 @@
+use core::fmt;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error;
+use std::io;
+use std::str;
+
+const SHOW_RELATIONS: &[Relation] = &[Relation::Overlaps, Relation::OverlappedBy];
+
+#[derive(Debug)]
+struct ParseError {
+    input: String,
+}
+
+impl ParseError {
+    fn err<T>(input: &str) -> Result<T, ParseError> {
+        Err(ParseError {
+            input: input.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ParseError({:?})", self.input)
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Relation {
+    Before,
+    Meets,
+    Overlaps,
+    Starts,
+    During,
+    Finishes,
+    Equals,
+    FinishedBy,
+    Contains,
+    StartedBy,
+    OverlappedBy,
+    MetBy,
+    After,
+}
+
+impl Relation {
+    const ALL: [Relation; 13] = [
+        Relation::Before,
+        Relation::Meets,
+        Relation::Overlaps,
+        Relation::Starts,
+        Relation::During,
+        Relation::Finishes,
+        Relation::Equals,
+        Relation::FinishedBy,
+        Relation::Contains,
+        Relation::StartedBy,
+        Relation::OverlappedBy,
+        Relation::MetBy,
+        Relation::After,
+    ];
+}
+
+#[derive(Debug)]
+struct Assignment {
+    start: i32,
+    end: i32,
+}
+
+impl Assignment {
+    fn relation(&self, other: &Assignment) -> Relation {
+        // Sections are whole units, so compare as half-open intervals [start, end + 1).
+        // Adjacent assignments like 2-4 and 5-6 then meet rather than being disjoint.
+        let (s1, e1) = (self.start, self.end + 1);
+        let (s2, e2) = (other.start, other.end + 1);
+
+        if e1 < s2 {
+            return Relation::Before;
+        }
+        if e1 == s2 {
+            return Relation::Meets;
+        }
+        if e2 < s1 {
+            return Relation::After;
+        }
+        if e2 == s1 {
+            return Relation::MetBy;
+        }
+
+        match (s1.cmp(&s2), e1.cmp(&e2)) {
+            (Ordering::Less, Ordering::Less) => Relation::Overlaps,
+            (Ordering::Less, Ordering::Equal) => Relation::FinishedBy,
+            (Ordering::Less, Ordering::Greater) => Relation::Contains,
+            (Ordering::Equal, Ordering::Less) => Relation::Starts,
+            (Ordering::Equal, Ordering::Equal) => Relation::Equals,
+            (Ordering::Equal, Ordering::Greater) => Relation::StartedBy,
+            (Ordering::Greater, Ordering::Less) => Relation::During,
+            (Ordering::Greater, Ordering::Equal) => Relation::Finishes,
+            (Ordering::Greater, Ordering::Greater) => Relation::OverlappedBy,
+        }
+    }
+}
+
+impl str::FromStr for Assignment {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('-').collect();
+        match parts.as_slice() {
+            &[start_str, end_str] => {
+                let start = start_str.parse().or(ParseError::err(s))?;
+                let end = end_str.parse().or(ParseError::err(s))?;
+                Ok(Assignment { start, end })
+            }
+            _ => ParseError::err(s),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Pair(Assignment, Assignment);
+
+impl Pair {
+    fn relation(&self) -> Relation {
+        self.0.relation(&self.1)
+    }
+}
+
+impl str::FromStr for Pair {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split(',').collect();
+        match parts.as_slice() {
+            &[l_str, r_str] => {
+                let l = l_str.parse().or(ParseError::err(s))?;
+                let r = r_str.parse().or(ParseError::err(s))?;
+                Ok(Pair(l, r))
+            }
+            _ => ParseError::err(s),
+        }
+    }
+}
+
+fn main() {
+    let input: Vec<_> = io::stdin().lines().map(Result::unwrap).collect();
+    let pairs: Vec<Pair> = input.iter().map(|line| line.parse().unwrap()).collect();
+
+    let mut counts: HashMap<Relation, usize> = HashMap::new();
+    for pair in &pairs {
+        *counts.entry(pair.relation()).or_default() += 1;
+    }
+
+    for relation in Relation::ALL {
+        let count = counts.get(&relation).copied().unwrap_or(0);
+        println!("{:?}: {}", relation, count);
+    }
+
+    let shown = pairs
+        .iter()
+        .zip(&input)
+        .enumerate()
+        .filter(|(_, (pair, _))| SHOW_RELATIONS.contains(&pair.relation()));
+
+    for (i, (pair, line)) in shown {
+        println!("{}: {} {:?}", i + 1, line, pair.relation());
+    }
+}
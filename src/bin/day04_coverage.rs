@@ -0,0 +1,223 @@
+use core::fmt;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::error;
+use std::io;
+use std::str;
+
+#[derive(Debug)]
+struct ParseError {
+    input: String,
+}
+
+impl ParseError {
+    fn err<T>(input: &str) -> Result<T, ParseError> {
+        Err(ParseError {
+            input: input.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ParseError({:?})", self.input)
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Debug)]
+struct Assignment {
+    start: i32,
+    end: i32,
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl str::FromStr for Assignment {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split('-').collect();
+        match parts.as_slice() {
+            &[start_str, end_str] => {
+                let start = start_str.parse().or(ParseError::err(s))?;
+                let end = end_str.parse().or(ParseError::err(s))?;
+                Ok(Assignment { start, end })
+            }
+            _ => ParseError::err(s),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Pair(Assignment, Assignment);
+
+impl str::FromStr for Pair {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split(',').collect();
+        match parts.as_slice() {
+            &[l_str, r_str] => {
+                let l = l_str.parse().or(ParseError::err(s))?;
+                let r = r_str.parse().or(ParseError::err(s))?;
+                Ok(Pair(l, r))
+            }
+            _ => ParseError::err(s),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Elf {
+    line: usize,
+    index: usize,
+    assignment: Assignment,
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} elf {}: {}",
+            self.line, self.index, self.assignment
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Coverage {
+    gaps: Vec<(i32, i32)>,
+    max_elves: usize,
+    max_elves_at: Vec<(i32, i32)>,
+}
+
+fn coverage(elves: &[Elf]) -> Coverage {
+    // Net change in the number of elves at each section boundary
+    let mut deltas: BTreeMap<i32, isize> = BTreeMap::new();
+    for elf in elves {
+        *deltas.entry(elf.assignment.start).or_default() += 1;
+        *deltas.entry(elf.assignment.end + 1).or_default() -= 1;
+    }
+
+    let mut gaps = vec![];
+    let mut max_elves = 0;
+    let mut max_elves_at = vec![];
+
+    let mut n_elves: isize = 0;
+    let mut boundaries = deltas.iter().peekable();
+    while let Some((&start, &delta)) = boundaries.next() {
+        n_elves += delta;
+        let Some((&next, _)) = boundaries.peek() else {
+            break;
+        };
+        let span = (start, next - 1);
+
+        if n_elves == 0 {
+            gaps.push(span);
+            continue;
+        }
+
+        let n = n_elves as usize;
+        if n > max_elves {
+            max_elves = n;
+            max_elves_at.clear();
+        }
+        if n == max_elves {
+            max_elves_at.push(span);
+        }
+    }
+
+    Coverage {
+        gaps,
+        max_elves,
+        max_elves_at,
+    }
+}
+
+fn minimal_cover(elves: &[Elf]) -> HashSet<usize> {
+    let mut order: Vec<usize> = (0..elves.len()).collect();
+    order.sort_by_key(|&i| elves[i].assignment.start);
+
+    let mut keep = HashSet::new();
+    let mut covered_to: Option<i32> = None;
+
+    let mut i = 0;
+    while i < order.len() {
+        let first_start = elves[order[i]].assignment.start;
+        let need = match covered_to {
+            Some(c) if first_start <= c + 1 => c + 1,
+            _ => first_start,
+        };
+
+        // Of everything that could cover the next needed section, keep the one reaching furthest
+        let mut best: Option<usize> = None;
+        while i < order.len() && elves[order[i]].assignment.start <= need {
+            let candidate = order[i];
+            if best.is_none_or(|b| elves[candidate].assignment.end > elves[b].assignment.end) {
+                best = Some(candidate);
+            }
+            i += 1;
+        }
+
+        let best = best.unwrap();
+        let best_end = elves[best].assignment.end;
+        if covered_to.is_none_or(|c| best_end > c) {
+            keep.insert(best);
+            covered_to = Some(best_end);
+        }
+    }
+
+    keep
+}
+
+fn fmt_spans(spans: &[(i32, i32)]) -> String {
+    let strs: Vec<_> = spans
+        .iter()
+        .map(|(start, end)| format!("{}-{}", start, end))
+        .collect();
+    strs.join(",")
+}
+
+fn main() {
+    let input = io::stdin().lines().map(Result::unwrap);
+    let pairs = input.map(|line| line.parse::<Pair>().unwrap());
+
+    let elves: Vec<Elf> = pairs
+        .enumerate()
+        .flat_map(|(i, Pair(l, r))| {
+            [
+                Elf {
+                    line: i + 1,
+                    index: 1,
+                    assignment: l,
+                },
+                Elf {
+                    line: i + 1,
+                    index: 2,
+                    assignment: r,
+                },
+            ]
+        })
+        .collect();
+
+    let cov = coverage(&elves);
+    println!("uncovered: {}", fmt_spans(&cov.gaps));
+    println!(
+        "max elves: {} at {}",
+        cov.max_elves,
+        fmt_spans(&cov.max_elves_at)
+    );
+
+    let keep = minimal_cover(&elves);
+    let droppable: Vec<_> = (0..elves.len()).filter(|i| !keep.contains(i)).collect();
+    println!("droppable: {} of {}", droppable.len(), elves.len());
+    for i in droppable {
+        println!("{}", elves[i]);
+    }
+}
@@ -0,0 +1,395 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::process;
+use std::str;
+
+const DUMP_MOVES: bool = false;
+
+// Skip and report invalid moves instead of stopping at the first one
+const LENIENT: bool = false;
+
+const CRANES: &[(&str, &dyn Crane)] = &[
+    ("CrateMover 9000", &CrateMover9000),
+    ("CrateMover 9001", &CrateMover9001),
+    ("bottom-first", &BottomFirst),
+    ("capacity 2", &CapacityLimited { capacity: 2 }),
+];
+
+#[derive(Debug)]
+struct Move {
+    n: usize,
+    src: usize,
+    dst: usize,
+}
+
+impl str::FromStr for Move {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_whitespace().collect();
+        match tokens.as_slice() {
+            &[_, n_str, _, src_str, _, dst_str] => {
+                let n: usize = n_str.parse().or(Err(()))?;
+                let src: usize = src_str.parse().or(Err(()))?;
+                let dst: usize = dst_str.parse().or(Err(()))?;
+                Ok(Move {
+                    n,
+                    src: src.checked_sub(1).ok_or(())?,
+                    dst: dst.checked_sub(1).ok_or(())?,
+                })
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.n,
+            self.src + 1,
+            self.dst + 1
+        )
+    }
+}
+
+#[derive(Debug)]
+struct DiagramError {
+    line: usize,
+    reason: String,
+}
+
+impl DiagramError {
+    fn err<T>(line: usize, reason: String) -> Result<T, DiagramError> {
+        Err(DiagramError { line, reason })
+    }
+}
+
+impl fmt::Display for DiagramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl error::Error for DiagramError {}
+
+#[derive(Debug)]
+struct MoveError {
+    line: usize,
+    stack_sizes: Vec<usize>,
+    reason: String,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {} (stack sizes {:?})",
+            self.line, self.reason, self.stack_sizes
+        )
+    }
+}
+
+impl error::Error for MoveError {}
+
+trait Crane {
+    fn apply(&self, stacks: &mut [Vec<String>], m: &Move);
+}
+
+// Lifts one crate at a time, so the moved crates end up reversed.
+struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn apply(&self, stacks: &mut [Vec<String>], m: &Move) {
+        for _ in 0..m.n {
+            let c = stacks[m.src].pop().unwrap();
+            stacks[m.dst].push(c);
+        }
+    }
+}
+
+// Lifts all the crates at once, so the moved crates keep their order.
+struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn apply(&self, stacks: &mut [Vec<String>], m: &Move) {
+        let src = &mut stacks[m.src];
+        let moved = src.split_off(src.len() - m.n);
+        stacks[m.dst].extend(moved);
+    }
+}
+
+// Pulls crates out from the bottom of the source stack, keeping their order.
+struct BottomFirst;
+
+impl Crane for BottomFirst {
+    fn apply(&self, stacks: &mut [Vec<String>], m: &Move) {
+        let moved: Vec<String> = stacks[m.src].drain(..m.n).collect();
+        stacks[m.dst].extend(moved);
+    }
+}
+
+// Lifts at most `capacity` crates at a time, keeping the order within each lift.
+struct CapacityLimited {
+    capacity: usize,
+}
+
+impl Crane for CapacityLimited {
+    fn apply(&self, stacks: &mut [Vec<String>], m: &Move) {
+        let mut remaining = m.n;
+        while remaining > 0 {
+            let n = remaining.min(self.capacity);
+            CrateMover9001.apply(stacks, &Move { n, ..*m });
+            remaining -= n;
+        }
+    }
+}
+
+// Byte ranges of the whitespace-separated tokens in a line
+fn token_spans(line: &str) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut start = None;
+
+    for (i, c) in line.char_indices() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, line.len()));
+    }
+
+    spans
+}
+
+fn parse_stacks(stacks_input: &[String]) -> Result<Vec<Vec<String>>, DiagramError> {
+    let Some((stacks_labels, stacks_contents)) = stacks_input.split_last() else {
+        return DiagramError::err(1, "missing stack labels".to_owned());
+    };
+    let labels_line = stacks_input.len();
+
+    // Each stack's column is wherever its label is written
+    let columns = token_spans(stacks_labels);
+    for (i, &(start, end)) in columns.iter().enumerate() {
+        let label = &stacks_labels[start..end];
+        if label != (i + 1).to_string() {
+            return DiagramError::err(
+                labels_line,
+                format!("expected stack label {}, found {:?}", i + 1, label),
+            );
+        }
+    }
+
+    let mut stacks: Vec<Vec<String>> = vec![vec![]; columns.len()];
+
+    for (i, line) in stacks_contents.iter().enumerate().rev() {
+        let line_no = i + 1;
+        let level = stacks_contents.len() - 1 - i;
+
+        for (start, end) in token_spans(line) {
+            let cell = &line[start..end];
+            let Some(label) = cell.strip_prefix('[').and_then(|c| c.strip_suffix(']')) else {
+                return DiagramError::err(line_no, format!("malformed crate {:?}", cell));
+            };
+            if label.is_empty() || label.contains(['[', ']']) {
+                return DiagramError::err(line_no, format!("malformed crate {:?}", cell));
+            }
+
+            // Crates and labels are centred in their column, give or take rounding
+            let aligned = columns
+                .iter()
+                .position(|&(col_start, col_end)| (start + end).abs_diff(col_start + col_end) <= 1);
+            let Some(stack) = aligned else {
+                return DiagramError::err(
+                    line_no,
+                    format!(
+                        "crate {} at column {} is not aligned with a stack label",
+                        cell,
+                        start + 1
+                    ),
+                );
+            };
+
+            if stacks[stack].len() > level {
+                return DiagramError::err(
+                    line_no,
+                    format!(
+                        "crate {} overlaps another crate in stack {}",
+                        cell,
+                        stack + 1
+                    ),
+                );
+            }
+            if stacks[stack].len() < level {
+                return DiagramError::err(
+                    line_no,
+                    format!(
+                        "crate {} in stack {} has nothing beneath it",
+                        cell,
+                        stack + 1
+                    ),
+                );
+            }
+            stacks[stack].push(label.to_owned());
+        }
+    }
+
+    Ok(stacks)
+}
+
+fn render_stacks(stacks: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = stacks
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let label_width = (i + 1).to_string().len() + 2;
+            let crate_width = s.iter().map(|c| c.len() + 2).max().unwrap_or(0);
+            label_width.max(crate_width)
+        })
+        .collect();
+
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines: Vec<String> = vec![];
+
+    for level in (0..height).rev() {
+        let cells: Vec<_> = stacks
+            .iter()
+            .zip(&widths)
+            .map(|(s, &width)| match s.get(level) {
+                Some(c) => format!("{:^width$}", format!("[{}]", c)),
+                None => " ".repeat(width),
+            })
+            .collect();
+        lines.push(cells.join(" "));
+    }
+
+    let labels: Vec<_> = widths
+        .iter()
+        .enumerate()
+        .map(|(i, &width)| format!("{:^width$}", i + 1))
+        .collect();
+    lines.push(labels.join(" "));
+
+    lines.join("\n")
+}
+
+fn validate(stacks: &[Vec<String>], m: &Move) -> Result<(), String> {
+    let n_stacks = stacks.len();
+    if m.src >= n_stacks {
+        return Err(format!("{}: no stack {}", m, m.src + 1));
+    }
+    if m.dst >= n_stacks {
+        return Err(format!("{}: no stack {}", m, m.dst + 1));
+    }
+    if m.src == m.dst {
+        return Err(format!("{}: source and destination are the same", m));
+    }
+    let available = stacks[m.src].len();
+    if m.n > available {
+        return Err(format!(
+            "{}: stack {} only has {} crates",
+            m,
+            m.src + 1,
+            available
+        ));
+    }
+    Ok(())
+}
+
+// Returns the invalid moves that were skipped, which is always empty unless LENIENT
+fn simulate(
+    stacks: &mut [Vec<String>],
+    moves: &[(usize, Move)],
+    crane: &dyn Crane,
+) -> Result<Vec<MoveError>, MoveError> {
+    let mut skipped = vec![];
+
+    for (line, m) in moves {
+        if let Err(reason) = validate(stacks, m) {
+            let err = MoveError {
+                line: *line,
+                stack_sizes: stacks.iter().map(Vec::len).collect(),
+                reason,
+            };
+            if LENIENT {
+                skipped.push(err);
+                continue;
+            }
+            return Err(err);
+        }
+
+        crane.apply(stacks, m);
+
+        if DUMP_MOVES {
+            println!("{}", m);
+            println!("{}", render_stacks(stacks));
+            println!();
+        }
+    }
+
+    Ok(skipped)
+}
+
+fn run() -> Result<(), Box<dyn error::Error>> {
+    let input: Vec<_> = io::stdin().lines().map(Result::unwrap).collect();
+    let input_sections: Vec<_> = input.splitn(2, String::is_empty).collect();
+
+    let (stacks_input, moves_input) = match input_sections.as_slice() {
+        &[stacks_input, moves_input] => (stacks_input, moves_input),
+        _ => panic!("wrong number of sections"),
+    };
+
+    let initial_stacks = parse_stacks(stacks_input)?;
+
+    // Rendering should round-trip, but that's only worth a second parse in debug builds
+    if cfg!(debug_assertions) {
+        let rendered: Vec<String> = render_stacks(&initial_stacks)
+            .lines()
+            .map(str::to_owned)
+            .collect();
+        assert_eq!(parse_stacks(&rendered)?, initial_stacks);
+    }
+
+    let moves_first_line = stacks_input.len() + 2;
+    let mut moves: Vec<(usize, Move)> = vec![];
+    for (i, m) in moves_input.iter().enumerate() {
+        let line = moves_first_line + i;
+        let m = m
+            .parse()
+            .map_err(|()| format!("line {}: can't parse move: {:?}", line, m))?;
+        moves.push((line, m));
+    }
+
+    for (name, crane) in CRANES {
+        let mut stacks = initial_stacks.clone();
+        let skipped = simulate(&mut stacks, &moves, *crane)?;
+        for err in skipped {
+            eprintln!("{}: skipped {}", name, err);
+        }
+
+        let tops: String = stacks
+            .iter()
+            .filter_map(|s| s.last())
+            .map(String::as_str)
+            .collect();
+        println!("{}: {}", name, tops);
+    }
+
+    Ok(())
+}
+
+// Returning the error from main would only show its Debug form
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
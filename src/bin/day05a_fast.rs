@@ -3,6 +3,22 @@ use std::str;
 
 const PART: usize = 1;
 
+const CRANE: Crane = if PART == 1 {
+    Crane::CrateMover9000
+} else {
+    Crane::CrateMover9001
+};
+
+// Positions to look up after all moves, or every stack's top crate if empty. Stacks count from 0,
+// unlike the labels in the input, and depth counts down from the top crate, which is at depth 0.
+const QUERIES: &[CratePos] = &[];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Crane {
+    CrateMover9000,
+    CrateMover9001,
+}
+
 #[derive(Debug)]
 struct Move {
     n: usize,
@@ -31,12 +47,45 @@ impl str::FromStr for Move {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 struct CratePos {
     stack: usize,
     depth: usize,
 }
 
+// Follows each final position backwards through the moves to where that crate started.
+fn trace_back(moves: &[Move], positions: &mut [CratePos], crane: Crane) {
+    for m in moves.iter().rev() {
+        if m.src == m.dst {
+            panic!("src == dst")
+        }
+        for cr in positions.iter_mut() {
+            if cr.stack == m.src {
+                cr.depth += m.n;
+            }
+            if cr.stack == m.dst {
+                if cr.depth >= m.n {
+                    cr.depth -= m.n;
+                } else {
+                    cr.stack = m.src;
+                    if crane == Crane::CrateMover9000 {
+                        cr.depth = m.n - cr.depth - 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn crate_at(stacks: &[Vec<char>], pos: CratePos) -> Option<char> {
+    let s = &stacks[pos.stack];
+    if pos.depth < s.len() {
+        Some(s[s.len() - pos.depth - 1])
+    } else {
+        None
+    }
+}
+
 fn main() {
     let input: Vec<_> = io::stdin().lines().map(Result::unwrap).collect();
     let input_sections: Vec<_> = input.splitn(2, String::is_empty).collect();
@@ -61,42 +110,39 @@ fn main() {
 
     let moves: Vec<Move> = moves_input.iter().map(|m| m.parse().unwrap()).collect();
 
-    let mut eventual_tops: Vec<CratePos> = (0..n_stacks)
-        .map(|i| CratePos { stack: i, depth: 0 })
-        .collect();
+    if QUERIES.is_empty() {
+        let mut eventual_tops: Vec<CratePos> = (0..n_stacks)
+            .map(|i| CratePos { stack: i, depth: 0 })
+            .collect();
+        trace_back(&moves, &mut eventual_tops, CRANE);
 
-    for m in moves.iter().rev() {
-        if m.src == m.dst {
-            panic!("src == dst")
-        }
-        for cr in eventual_tops.iter_mut() {
-            if cr.stack == m.src {
-                cr.depth += m.n;
-            }
-            if cr.stack == m.dst {
-                if cr.depth >= m.n {
-                    cr.depth -= m.n;
-                } else {
-                    cr.stack = m.src;
-                    if PART == 1 {
-                        cr.depth = m.n - cr.depth - 1;
-                    }
-                }
-            }
+        let tops: String = eventual_tops
+            .iter()
+            .filter_map(|&cr| crate_at(&stacks, cr))
+            .collect();
+
+        println!("{}", tops);
+    } else {
+        for query in QUERIES {
+            assert!(
+                query.stack < n_stacks,
+                "query for stack {}, but there are only {}",
+                query.stack + 1,
+                n_stacks
+            );
         }
-    }
 
-    let tops: String = eventual_tops
-        .iter()
-        .filter_map(|cr| {
-            let s = &stacks[cr.stack];
-            if cr.depth < s.len() {
-                Some(s[s.len() - cr.depth - 1])
-            } else {
-                None
-            }
-        })
-        .collect();
+        let mut origins = QUERIES.to_vec();
+        trace_back(&moves, &mut origins, CRANE);
 
-    println!("{}", tops);
+        for (query, &origin) in QUERIES.iter().zip(&origins) {
+            let c = crate_at(&stacks, origin).unwrap_or('-');
+            println!(
+                "stack {}, depth {} from the top: {}",
+                query.stack + 1,
+                query.depth,
+                c
+            );
+        }
+    }
 }
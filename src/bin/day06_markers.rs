@@ -0,0 +1,70 @@
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+
+const MARKER_SIZES: &[usize] = &[4, 14];
+const BUF_SIZE: usize = 1 << 16;
+
+// For each marker size, every offset at which the preceding `size` bytes are all distinct.
+fn all_markers(input: impl Read, marker_sizes: &[usize]) -> io::Result<Vec<Vec<usize>>> {
+    let mut reader = BufReader::with_capacity(BUF_SIZE, input);
+    let mut markers = vec![vec![]; marker_sizes.len()];
+
+    // Offset just past the latest occurrence of each byte
+    let mut last_seen = [0; 256];
+    // Length of the longest run of distinct bytes ending at the current offset
+    let mut run = 0;
+    let mut offset = 0;
+    // The last two bytes read, to spot the stream's final line ending
+    let mut last_two = [0; 2];
+
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+
+        match *buf {
+            [.., a, b] => last_two = [a, b],
+            [b] => last_two = [last_two[1], b],
+            [] => {}
+        }
+
+        for &b in buf {
+            offset += 1;
+            run = Ord::min(run + 1, offset - last_seen[b as usize]);
+            last_seen[b as usize] = offset;
+
+            for (size, offsets) in marker_sizes.iter().zip(markers.iter_mut()) {
+                if run >= *size {
+                    offsets.push(offset);
+                }
+            }
+        }
+
+        let len = buf.len();
+        reader.consume(len);
+    }
+
+    // The final "\n" or "\r\n" isn't data, so drop markers that end inside it
+    let ending = match last_two {
+        [b'\r', b'\n'] => 2,
+        [_, b'\n'] => 1,
+        _ => 0,
+    };
+    for offsets in &mut markers {
+        offsets.retain(|&o| o <= offset - ending);
+    }
+
+    Ok(markers)
+}
+
+fn main() {
+    let markers = all_markers(io::stdin().lock(), MARKER_SIZES).unwrap();
+
+    for (size, offsets) in MARKER_SIZES.iter().zip(markers) {
+        let strs: Vec<_> = offsets.iter().map(usize::to_string).collect();
+        println!("{}: {}", size, strs.join(","));
+    }
+}
@@ -0,0 +1,86 @@
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+
+const MARKER_SIZE: usize = 14;
+const BUF_SIZE: usize = 1 << 16;
+
+struct Counter {
+    counts: [usize; 256],
+    distinct: usize,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Self {
+            counts: [0; 256],
+            distinct: 0,
+        }
+    }
+
+    fn add(&mut self, b: u8) {
+        let i = b as usize;
+        if self.counts[i] == 0 {
+            self.distinct += 1;
+        }
+        self.counts[i] += 1;
+    }
+
+    fn remove(&mut self, b: u8) {
+        let i = b as usize;
+        self.counts[i] -= 1;
+        if self.counts[i] == 0 {
+            self.distinct -= 1;
+        }
+    }
+}
+
+fn bytes_until_marker(input: impl Read, marker_size: usize) -> io::Result<Option<usize>> {
+    assert!(marker_size > 0);
+
+    let mut reader = BufReader::with_capacity(BUF_SIZE, input);
+    let mut ctr = Counter::new();
+    // Ring buffer of the last `marker_size` bytes
+    let mut window = vec![0; marker_size];
+    let mut offset = 0;
+
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(None);
+        }
+
+        for (i, &b) in buf.iter().enumerate() {
+            let slot = offset % marker_size;
+            if offset >= marker_size {
+                ctr.remove(window[slot]);
+            }
+            window[slot] = b;
+            ctr.add(b);
+            offset += 1;
+
+            if ctr.distinct == marker_size {
+                // The stream's final "\n" or "\r\n" isn't data, so a marker ending in it isn't one
+                if b == b'\r' || b == b'\n' {
+                    reader.consume(i);
+                    let mut rest = vec![];
+                    (&mut reader).take(3).read_to_end(&mut rest)?;
+                    if rest == b"\n" || rest == b"\r\n" {
+                        return Ok(None);
+                    }
+                }
+
+                return Ok(Some(offset));
+            }
+        }
+
+        let len = buf.len();
+        reader.consume(len);
+    }
+}
+
+fn main() {
+    let offset = bytes_until_marker(io::stdin().lock(), MARKER_SIZE).unwrap();
+    println!("{}", offset.unwrap());
+}
@@ -0,0 +1,236 @@
+#![feature(portable_simd)]
+
+use std::io;
+use std::io::Read;
+use std::simd::prelude::*;
+use std::time::Duration;
+use std::time::Instant;
+
+const MARKER_SIZE: usize = 14;
+
+// Each search is timed this many times, keeping the fastest
+const REPEATS: usize = 5;
+// Throughput isn't reported when less than this much input was scanned
+const MIN_BENCH_BYTES: usize = 1 << 24;
+
+// Positions compared at once by the SIMD search. Each block also looks at the following
+// MARKER_SIZE - 1 bytes, so it decides BLOCK_STEP window starts.
+const BLOCK: usize = 64;
+const BLOCK_STEP: usize = BLOCK - (MARKER_SIZE - 1);
+const _: () = assert!(MARKER_SIZE >= 1 && MARKER_SIZE <= BLOCK);
+
+type Search = fn(&[u8]) -> Option<usize>;
+
+struct Counter {
+    counts: [usize; 256],
+    distinct: usize,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Self {
+            counts: [0; 256],
+            distinct: 0,
+        }
+    }
+
+    fn add(&mut self, b: u8) {
+        let i = b as usize;
+        if self.counts[i] == 0 {
+            self.distinct += 1;
+        }
+        self.counts[i] += 1;
+    }
+
+    fn remove(&mut self, b: u8) {
+        let i = b as usize;
+        self.counts[i] -= 1;
+        if self.counts[i] == 0 {
+            self.distinct -= 1;
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ByteSet([u64; 4]);
+
+impl ByteSet {
+    fn new() -> Self {
+        Self([0; 4])
+    }
+
+    // Returns whether the byte is now in the set
+    fn toggle(&mut self, b: u8) -> bool {
+        let word = &mut self.0[(b / 64) as usize];
+        *word ^= 1 << (b % 64);
+        *word & (1 << (b % 64)) != 0
+    }
+}
+
+fn bytes_until_marker(data: &[u8]) -> Option<usize> {
+    let mut ctr = Counter::new();
+
+    for b in data.iter().take(MARKER_SIZE - 1) {
+        ctr.add(*b);
+    }
+
+    for (offset, window) in data.windows(MARKER_SIZE).enumerate() {
+        ctr.add(*window.last().unwrap());
+
+        if ctr.distinct == MARKER_SIZE {
+            return Some(offset + MARKER_SIZE);
+        }
+
+        ctr.remove(*window.first().unwrap());
+    }
+
+    None
+}
+
+// A byte that appears an even number of times in the window cancels out of the XOR, so the
+// window is a marker exactly when the mask has one bit per byte.
+fn bytes_until_marker_xor(data: &[u8]) -> Option<usize> {
+    let mut mask = ByteSet::new();
+    let mut bits: usize = 0;
+
+    let mut toggle = |b| {
+        if mask.toggle(b) {
+            bits += 1;
+        } else {
+            bits -= 1;
+        }
+        bits
+    };
+
+    for (offset, &b) in data.iter().enumerate() {
+        let mut n_bits = toggle(b);
+        if offset >= MARKER_SIZE {
+            n_bits = toggle(data[offset - MARKER_SIZE]);
+        }
+
+        if n_bits == MARKER_SIZE {
+            return Some(offset + 1);
+        }
+    }
+
+    None
+}
+
+// The window can never start at or before an earlier copy of the byte just read, so `start` jumps
+// straight past it instead of sliding one byte at a time.
+fn bytes_until_marker_skip(data: &[u8]) -> Option<usize> {
+    // Offset just past the latest occurrence of each byte
+    let mut last_seen = [0; 256];
+    let mut start = 0;
+
+    for (offset, &b) in data.iter().enumerate() {
+        start = Ord::max(start, last_seen[b as usize]);
+        last_seen[b as usize] = offset + 1;
+
+        if offset + 1 - start == MARKER_SIZE {
+            return Some(offset + 1);
+        }
+    }
+
+    None
+}
+
+// Bit i of the result is set if the window starting at `start + i` has a repeated byte, for i
+// below BLOCK_STEP. Comparing the block against itself shifted by each distance d finds the
+// positions whose byte repeats d bytes later, and a repeat at distance d spoils the windows
+// starting up to MARKER_SIZE - 1 - d bytes before it. Folding the distances in order, and
+// smearing by one more byte each time, spreads every repeat over exactly those windows.
+#[inline(always)]
+fn repeats_in_block(data: &[u8], start: usize) -> u64 {
+    let block = u8x64::from_slice(&data[start..start + BLOCK]);
+
+    let mut bad: u64 = 0;
+    for d in 1..MARKER_SIZE {
+        let shifted = u8x64::from_slice(&data[start + d..start + d + BLOCK]);
+        bad = bad | (bad >> 1) | block.simd_eq(shifted).to_bitmask();
+    }
+
+    bad
+}
+
+#[inline(always)]
+fn bytes_until_marker_blocks(data: &[u8]) -> Option<usize> {
+    let mut start = 0;
+
+    while start + BLOCK + MARKER_SIZE - 1 <= data.len() {
+        let good = !repeats_in_block(data, start) & (u64::MAX >> (64 - BLOCK_STEP));
+        if good != 0 {
+            return Some(start + good.trailing_zeros() as usize + MARKER_SIZE);
+        }
+
+        start += BLOCK_STEP;
+    }
+
+    bytes_until_marker_skip(&data[start..]).map(|offset| offset + start)
+}
+
+// The same search compiled for wider vectors, which the default target splits up
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512bw")]
+fn bytes_until_marker_blocks_avx512(data: &[u8]) -> Option<usize> {
+    bytes_until_marker_blocks(data)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+fn bytes_until_marker_blocks_avx2(data: &[u8]) -> Option<usize> {
+    bytes_until_marker_blocks(data)
+}
+
+fn bytes_until_marker_simd(data: &[u8]) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SAFETY: each is only called once the CPU is known to support its target feature
+        if is_x86_feature_detected!("avx512bw") {
+            return unsafe { bytes_until_marker_blocks_avx512(data) };
+        }
+        if is_x86_feature_detected!("avx2") {
+            return unsafe { bytes_until_marker_blocks_avx2(data) };
+        }
+    }
+
+    bytes_until_marker_blocks(data)
+}
+
+fn main() {
+    let mut input = vec![];
+    io::stdin().read_to_end(&mut input).unwrap();
+    let data = input.trim_ascii_end();
+
+    let expected = bytes_until_marker(data);
+
+    let impls: [(&str, Search); 4] = [
+        ("counter", bytes_until_marker),
+        ("xor", bytes_until_marker_xor),
+        ("skip", bytes_until_marker_skip),
+        ("simd", bytes_until_marker_simd),
+    ];
+
+    for (name, f) in impls {
+        let mut fastest = Duration::MAX;
+        for _ in 0..REPEATS {
+            let start = Instant::now();
+            let offset = f(data);
+            fastest = Ord::min(fastest, start.elapsed());
+
+            assert_eq!(offset, expected, "{} disagrees with counter", name);
+        }
+
+        // Every search reads the input up to the end of the marker, or all of it
+        let scanned = expected.unwrap_or(data.len());
+        if scanned >= MIN_BENCH_BYTES {
+            let gbps = scanned as f64 / fastest.as_secs_f64() / 1e9;
+            println!("{}: {:?} ({:.2} GB/s)", name, expected, gbps);
+        } else {
+            println!(
+                "{}: {:?} ({:?} for {} bytes)",
+                name, expected, fastest, scanned
+            );
+        }
+    }
+}
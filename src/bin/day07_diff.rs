@@ -0,0 +1,243 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::iter::Peekable;
+
+#[derive(Debug)]
+enum LsEntry {
+    Dir { name: String },
+    File { size: usize, name: String },
+}
+
+#[derive(Debug)]
+enum Command {
+    Cd { path: String },
+    Ls { entries: Vec<LsEntry> },
+}
+
+impl Command {
+    fn read(output: &mut Peekable<impl Iterator<Item = String>>) -> Option<Self> {
+        let line = output.next()?;
+        let tokens: Vec<_> = line
+            .strip_prefix("$ ")
+            .unwrap()
+            .split_whitespace()
+            .collect();
+
+        match *tokens.as_slice() {
+            ["cd", path] => Some(Command::Cd {
+                path: path.to_owned(),
+            }),
+
+            ["ls"] => {
+                let mut nodes = vec![];
+                while let Some(line) = output.next_if(|l| !l.starts_with('$')) {
+                    let tokens: Vec<_> = line.split_whitespace().collect();
+                    let node = match *tokens.as_slice() {
+                        ["dir", name] => LsEntry::Dir {
+                            name: name.to_owned(),
+                        },
+                        [size, name] => LsEntry::File {
+                            size: size.parse().unwrap(),
+                            name: name.to_owned(),
+                        },
+                        _ => panic!(),
+                    };
+                    nodes.push(node);
+                }
+
+                Some(Command::Ls { entries: nodes })
+            }
+
+            _ => panic!(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Dir {
+    subdirs: HashMap<String, Dir>,
+    files: HashMap<String, usize>,
+    size: Option<usize>,
+}
+
+impl Dir {
+    fn new() -> Self {
+        Self {
+            subdirs: HashMap::new(),
+            files: HashMap::new(),
+            size: None,
+        }
+    }
+
+    fn get_dir_mut(&mut self, path: &[String]) -> Option<&mut Dir> {
+        match path {
+            [] => Some(self),
+            [first, rest @ ..] => self.subdirs.get_mut(first)?.get_dir_mut(rest),
+        }
+    }
+
+    fn update_size(&mut self) -> usize {
+        let subdirs_size: usize = self.subdirs.values_mut().map(Dir::update_size).sum();
+        let files_size: usize = self.files.values().sum();
+        let size = subdirs_size + files_size;
+        self.size = Some(size);
+        size
+    }
+}
+
+#[derive(Debug)]
+enum Change {
+    Added { size: usize },
+    Removed { size: usize },
+    Resized { old: usize, new: usize },
+}
+
+#[derive(Debug)]
+struct Diff {
+    path: String,
+    is_dir: bool,
+    change: Change,
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.is_dir { "dir " } else { "file" };
+        match self.change {
+            Change::Added { size } => write!(f, "+ {} {} {}", kind, self.path, size),
+            Change::Removed { size } => write!(f, "- {} {} {}", kind, self.path, size),
+            Change::Resized { old, new } => write!(
+                f,
+                "~ {} {} {} -> {} ({:+})",
+                kind,
+                self.path,
+                old,
+                new,
+                new as i64 - old as i64
+            ),
+        }
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    format!("{}/{}", path.trim_end_matches('/'), name)
+}
+
+// Lists changes from `old` to `new` in pre-order, with added or removed directories reported
+// as a whole rather than file by file
+fn diff_dirs(old: &Dir, new: &Dir, path: &str, diffs: &mut Vec<Diff>) {
+    let (old_size, new_size) = (old.size.unwrap(), new.size.unwrap());
+    if old_size != new_size {
+        diffs.push(Diff {
+            path: path.to_owned(),
+            is_dir: true,
+            change: Change::Resized {
+                old: old_size,
+                new: new_size,
+            },
+        });
+    }
+
+    let subdir_names: BTreeSet<_> = old.subdirs.keys().chain(new.subdirs.keys()).collect();
+    for name in subdir_names {
+        let subpath = join(path, name);
+        match (old.subdirs.get(name), new.subdirs.get(name)) {
+            (Some(old_subdir), Some(new_subdir)) => {
+                diff_dirs(old_subdir, new_subdir, &subpath, diffs);
+            }
+            (Some(old_subdir), None) => diffs.push(Diff {
+                path: subpath,
+                is_dir: true,
+                change: Change::Removed {
+                    size: old_subdir.size.unwrap(),
+                },
+            }),
+            (None, Some(new_subdir)) => diffs.push(Diff {
+                path: subpath,
+                is_dir: true,
+                change: Change::Added {
+                    size: new_subdir.size.unwrap(),
+                },
+            }),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    let file_names: BTreeSet<_> = old.files.keys().chain(new.files.keys()).collect();
+    for name in file_names {
+        let change = match (old.files.get(name), new.files.get(name)) {
+            (Some(&old), Some(&new)) if old == new => continue,
+            (Some(&old), Some(&new)) => Change::Resized { old, new },
+            (Some(&size), None) => Change::Removed { size },
+            (None, Some(&size)) => Change::Added { size },
+            (None, None) => unreachable!(),
+        };
+        diffs.push(Diff {
+            path: join(path, name),
+            is_dir: false,
+            change,
+        });
+    }
+}
+
+fn replay(output: &mut Peekable<impl Iterator<Item = String>>) -> Dir {
+    let first_command = Command::read(output).unwrap();
+    assert!(matches!(first_command, Command::Cd { path } if path == "/"));
+
+    let mut root = Dir::new();
+
+    let mut current_path: Vec<String> = vec![];
+
+    while let Some(command) = Command::read(output) {
+        match command {
+            Command::Cd { path } if path == ".." => {
+                assert!(current_path.pop().is_some());
+            }
+
+            Command::Cd { path } => {
+                current_path.push(path);
+                assert!(root.get_dir_mut(current_path.as_slice()).is_some());
+            }
+
+            Command::Ls { entries } => {
+                let current_dir = root.get_dir_mut(current_path.as_slice()).unwrap();
+
+                for entry in entries {
+                    match entry {
+                        LsEntry::Dir { name } => {
+                            assert!(current_dir.subdirs.insert(name, Dir::new()).is_none());
+                        }
+                        LsEntry::File { size, name } => {
+                            assert!(current_dir.files.insert(name, size).is_none());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    root
+}
+
+fn main() {
+    let input: Vec<_> = io::stdin().lines().map(Result::unwrap).collect();
+    let input_sections: Vec<_> = input.splitn(2, String::is_empty).collect();
+
+    let (old_input, new_input) = match input_sections.as_slice() {
+        &[old_input, new_input] => (old_input, new_input),
+        _ => panic!("expected two transcripts separated by a blank line"),
+    };
+
+    let mut old_root = replay(&mut old_input.iter().cloned().peekable());
+    let mut new_root = replay(&mut new_input.iter().cloned().peekable());
+    old_root.update_size();
+    new_root.update_size();
+
+    let mut diffs = vec![];
+    diff_dirs(&old_root, &new_root, "/", &mut diffs);
+
+    for diff in diffs {
+        println!("{}", diff);
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::iter::Peekable;
+use std::path::Path;
+
+// Read this directory from disk instead of reading a transcript from stdin
+const DISK_ROOT: Option<&str> = None;
+
+#[derive(Debug)]
+enum LsEntry {
+    Dir { name: String },
+    File { size: usize, name: String },
+}
+
+#[derive(Debug)]
+enum Command {
+    Cd { path: String },
+    Ls { entries: Vec<LsEntry> },
+}
+
+impl Command {
+    fn read(output: &mut Peekable<impl Iterator<Item = String>>) -> Option<Self> {
+        let line = output.next()?;
+        let tokens: Vec<_> = line
+            .strip_prefix("$ ")
+            .unwrap()
+            .split_whitespace()
+            .collect();
+
+        match *tokens.as_slice() {
+            ["cd", path] => Some(Command::Cd {
+                path: path.to_owned(),
+            }),
+
+            ["ls"] => {
+                let mut nodes = vec![];
+                while let Some(line) = output.next_if(|l| !l.starts_with('$')) {
+                    let tokens: Vec<_> = line.split_whitespace().collect();
+                    let node = match *tokens.as_slice() {
+                        ["dir", name] => LsEntry::Dir {
+                            name: name.to_owned(),
+                        },
+                        [size, name] => LsEntry::File {
+                            size: size.parse().unwrap(),
+                            name: name.to_owned(),
+                        },
+                        _ => panic!(),
+                    };
+                    nodes.push(node);
+                }
+
+                Some(Command::Ls { entries: nodes })
+            }
+
+            _ => panic!(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Dir {
+    subdirs: HashMap<String, Dir>,
+    files: HashMap<String, usize>,
+}
+
+impl Dir {
+    fn new() -> Self {
+        Self {
+            subdirs: HashMap::new(),
+            files: HashMap::new(),
+        }
+    }
+
+    fn get_dir(&self, path: &[String]) -> Option<&Dir> {
+        match path {
+            [] => Some(self),
+            [first, rest @ ..] => self.subdirs.get(first)?.get_dir(rest),
+        }
+    }
+
+    fn get_dir_mut(&mut self, path: &[String]) -> Option<&mut Dir> {
+        match path {
+            [] => Some(self),
+            [first, rest @ ..] => self.subdirs.get_mut(first)?.get_dir_mut(rest),
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.subdirs
+            .values()
+            .map(|d| d.height() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Lists every directory depth-first. Each directory takes one `cd` to enter and one `cd ..` to
+    // leave, except along the path to the last one, so the deepest subtree is left until last.
+    fn visit_order(&self, path: &mut Vec<String>, order: &mut Vec<Vec<String>>) {
+        order.push(path.clone());
+
+        let mut names: Vec<_> = self.subdirs.keys().collect();
+        names.sort_by_key(|name| (self.subdirs[*name].height(), *name));
+
+        for name in names {
+            path.push(name.clone());
+            self.subdirs[name].visit_order(path, order);
+            path.pop();
+        }
+    }
+
+    fn ls_output(&self) -> Vec<String> {
+        let mut subdir_names: Vec<_> = self.subdirs.keys().collect();
+        subdir_names.sort();
+        let mut file_names: Vec<_> = self.files.keys().collect();
+        file_names.sort();
+
+        let dirs = subdir_names.into_iter().map(|name| format!("dir {}", name));
+        let files = file_names
+            .into_iter()
+            .map(|name| format!("{} {}", self.files[name], name));
+        dirs.chain(files).collect()
+    }
+}
+
+// Emits a transcript that lists every directory. Only the first command is `cd /`, so that
+// day07a and day07b can read it, which leaves `cd ..` as the only way back up.
+fn emit_transcript(root: &Dir) -> Vec<String> {
+    let mut order = vec![];
+    root.visit_order(&mut vec![], &mut order);
+
+    let mut lines = vec!["$ cd /".to_owned()];
+    let mut cwd: Vec<String> = vec![];
+
+    for path in order {
+        let common = cwd.iter().zip(&path).take_while(|(a, b)| a == b).count();
+        for _ in common..cwd.len() {
+            lines.push("$ cd ..".to_owned());
+        }
+        for name in &path[common..] {
+            lines.push(format!("$ cd {}", name));
+        }
+
+        lines.push("$ ls".to_owned());
+        lines.extend(root.get_dir(&path).unwrap().ls_output());
+        cwd = path;
+    }
+
+    lines
+}
+
+fn read_disk(path: &Path) -> io::Result<Dir> {
+    let mut dir = Dir::new();
+
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        // Names are whitespace-separated in the transcript
+        if name.contains(char::is_whitespace) {
+            eprintln!("skipping {:?}", entry.path());
+            continue;
+        }
+
+        if file_type.is_dir() {
+            dir.subdirs.insert(name, read_disk(&entry.path())?);
+        } else if file_type.is_file() {
+            dir.files.insert(name, entry.metadata()?.len() as usize);
+        }
+    }
+
+    Ok(dir)
+}
+
+fn replay(output: &mut Peekable<impl Iterator<Item = String>>) -> Dir {
+    let first_command = Command::read(output).unwrap();
+    assert!(matches!(first_command, Command::Cd { path } if path == "/"));
+
+    let mut root = Dir::new();
+
+    let mut current_path: Vec<String> = vec![];
+
+    while let Some(command) = Command::read(output) {
+        match command {
+            Command::Cd { path } if path == ".." => {
+                assert!(current_path.pop().is_some());
+            }
+
+            Command::Cd { path } => {
+                current_path.push(path);
+                assert!(root.get_dir_mut(current_path.as_slice()).is_some());
+            }
+
+            Command::Ls { entries } => {
+                let current_dir = root.get_dir_mut(current_path.as_slice()).unwrap();
+
+                for entry in entries {
+                    match entry {
+                        LsEntry::Dir { name } => {
+                            assert!(current_dir.subdirs.insert(name, Dir::new()).is_none());
+                        }
+                        LsEntry::File { size, name } => {
+                            assert!(current_dir.files.insert(name, size).is_none());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    root
+}
+
+fn main() {
+    let root = match DISK_ROOT {
+        Some(path) => read_disk(Path::new(path)).unwrap(),
+        None => replay(&mut io::stdin().lines().map(Result::unwrap).peekable()),
+    };
+
+    let transcript = emit_transcript(&root);
+    assert_eq!(replay(&mut transcript.iter().cloned().peekable()), root);
+
+    for line in transcript {
+        println!("{}", line);
+    }
+}
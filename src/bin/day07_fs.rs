@@ -0,0 +1,354 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::io;
+use std::iter::Peekable;
+
+const FIND_NAME: &str = ".";
+const FIND_MIN_SIZE: usize = 100_000;
+
+// Record problems in the transcript as warnings instead of failing on the first one
+const TOLERANT: bool = true;
+
+#[derive(Debug)]
+enum LsEntry {
+    Dir {
+        line: usize,
+        name: String,
+    },
+    File {
+        line: usize,
+        size: usize,
+        name: String,
+    },
+    Invalid {
+        line: usize,
+        text: String,
+    },
+}
+
+#[derive(Debug)]
+enum Command {
+    Cd { path: String },
+    Ls { entries: Vec<LsEntry> },
+    Unknown { text: String },
+}
+
+impl Command {
+    // Reads the next command and its output, along with the line number it started on
+    fn read(output: &mut Peekable<impl Iterator<Item = (usize, String)>>) -> Option<(usize, Self)> {
+        let (line, text) = output.next()?;
+        let Some(command_text) = text.strip_prefix("$ ") else {
+            return Some((line, Command::Unknown { text }));
+        };
+        let tokens: Vec<_> = command_text.split_whitespace().collect();
+
+        let is_output = |(_, l): &(usize, String)| !l.starts_with('$');
+
+        let command = match *tokens.as_slice() {
+            ["cd", path] => Command::Cd {
+                path: path.to_owned(),
+            },
+
+            ["ls"] => {
+                let mut nodes = vec![];
+                while let Some((line, text)) = output.next_if(is_output) {
+                    let tokens: Vec<_> = text.split_whitespace().collect();
+                    let node = match *tokens.as_slice() {
+                        ["dir", name] => LsEntry::Dir {
+                            line,
+                            name: name.to_owned(),
+                        },
+                        [size, name] => match size.parse() {
+                            Ok(size) => LsEntry::File {
+                                line,
+                                size,
+                                name: name.to_owned(),
+                            },
+                            Err(_) => LsEntry::Invalid { line, text },
+                        },
+                        _ => LsEntry::Invalid { line, text },
+                    };
+                    nodes.push(node);
+                }
+
+                Command::Ls { entries: nodes }
+            }
+
+            _ => {
+                // Skip whatever the command printed
+                while output.next_if(is_output).is_some() {}
+                Command::Unknown { text }
+            }
+        };
+
+        Some((line, command))
+    }
+}
+
+#[derive(Debug)]
+struct Warning {
+    line: usize,
+    message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl error::Error for Warning {}
+
+// Resolves `path` relative to `cwd`, returning the components of an absolute path
+fn resolve(cwd: &[String], path: &str) -> Vec<String> {
+    let mut resolved = if path.starts_with('/') {
+        vec![]
+    } else {
+        cwd.to_vec()
+    };
+
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                resolved.pop();
+            }
+            name => resolved.push(name.to_owned()),
+        }
+    }
+
+    resolved
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.ends_with('/') {
+        format!("{}{}", path, name)
+    } else {
+        format!("{}/{}", path, name)
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    path: String,
+    name: String,
+    size: usize,
+    is_dir: bool,
+}
+
+#[derive(Debug)]
+struct Dir {
+    subdirs: BTreeMap<String, Dir>,
+    files: BTreeMap<String, usize>,
+    // Recursive size, cleared whenever anything below this directory changes
+    size: Cell<Option<usize>>,
+}
+
+impl Dir {
+    fn new() -> Self {
+        Self {
+            subdirs: BTreeMap::new(),
+            files: BTreeMap::new(),
+            size: Cell::new(None),
+        }
+    }
+
+    fn get_dir(&self, path: &[String]) -> Option<&Dir> {
+        match path {
+            [] => Some(self),
+            [first, rest @ ..] => self.subdirs.get(first)?.get_dir(rest),
+        }
+    }
+
+    // Invalidates cached sizes along the path, since the caller may modify what it returns
+    fn get_dir_mut(&mut self, path: &[String]) -> Option<&mut Dir> {
+        self.size.set(None);
+        match path {
+            [] => Some(self),
+            [first, rest @ ..] => self.subdirs.get_mut(first)?.get_dir_mut(rest),
+        }
+    }
+
+    // Like get_dir_mut, but creates any missing directories along the way
+    fn get_dir_mut_or_create(&mut self, path: &[String]) -> &mut Dir {
+        self.size.set(None);
+        match path {
+            [] => self,
+            [first, rest @ ..] => self
+                .subdirs
+                .entry(first.clone())
+                .or_insert_with(Dir::new)
+                .get_dir_mut_or_create(rest),
+        }
+    }
+
+    fn size(&self) -> usize {
+        if let Some(size) = self.size.get() {
+            return size;
+        }
+
+        let subdirs_size: usize = self.subdirs.values().map(Dir::size).sum();
+        let files_size: usize = self.files.values().sum();
+        let size = subdirs_size + files_size;
+        self.size.set(Some(size));
+        size
+    }
+
+    // Every file and directory below this one, in depth-first order
+    fn walk(&self, path: &str, entries: &mut Vec<Entry>) {
+        for (name, subdir) in &self.subdirs {
+            let subpath = join(path, name);
+            entries.push(Entry {
+                path: subpath.clone(),
+                name: name.clone(),
+                size: subdir.size(),
+                is_dir: true,
+            });
+            subdir.walk(&subpath, entries);
+        }
+        for (name, size) in &self.files {
+            entries.push(Entry {
+                path: join(path, name),
+                name: name.clone(),
+                size: *size,
+                is_dir: false,
+            });
+        }
+    }
+
+    fn find(&self, path: &str, pred: impl Fn(&Entry) -> bool) -> Vec<Entry> {
+        let mut entries = vec![];
+        self.walk(path, &mut entries);
+        entries.retain(pred);
+        entries
+    }
+
+    // Like `du`: every directory with its recursive size, subdirectories before their parent
+    fn du(&self, path: &str) -> Vec<(String, usize)> {
+        let mut listing = vec![];
+        for (name, subdir) in &self.subdirs {
+            listing.extend(subdir.du(&join(path, name)));
+        }
+        listing.push((path.to_owned(), self.size()));
+        listing
+    }
+
+    // Renders the tree in the same layout as the puzzle description
+    fn render(&self, name: &str) -> String {
+        let mut lines = vec![];
+        self.render_lines(name, 0, &mut lines);
+        lines.join("\n")
+    }
+
+    fn render_lines(&self, name: &str, depth: usize, lines: &mut Vec<String>) {
+        let indent = "  ".repeat(depth);
+        lines.push(format!("{}- {} (dir)", indent, name));
+        for (subname, subdir) in &self.subdirs {
+            subdir.render_lines(subname, depth + 1, lines);
+        }
+        for (filename, size) in &self.files {
+            lines.push(format!("{}  - {} (file, size={})", indent, filename, size));
+        }
+    }
+}
+
+// Rebuilds the filesystem from a transcript. Repeated listings of a directory are merged, and
+// directories that are entered before being listed are created. Anything else that doesn't fit
+// is a warning, which is an error unless `tolerant` is set.
+fn replay(
+    output: impl Iterator<Item = String>,
+    tolerant: bool,
+) -> Result<(Dir, Vec<Warning>), Warning> {
+    let mut output = (1..).zip(output).peekable();
+
+    let mut root = Dir::new();
+    let mut warnings = vec![];
+
+    let mut warn = |line: usize, message: String| {
+        let warning = Warning { line, message };
+        if tolerant {
+            warnings.push(warning);
+            Ok(())
+        } else {
+            Err(warning)
+        }
+    };
+
+    let mut current_path: Vec<String> = vec![];
+
+    while let Some((line, command)) = Command::read(&mut output) {
+        match command {
+            Command::Cd { path } if path == ".." && current_path.is_empty() => {
+                warn(line, "cd .. from /".to_owned())?;
+            }
+
+            Command::Cd { path } => {
+                current_path = resolve(&current_path, &path);
+                if root.get_dir(current_path.as_slice()).is_none() {
+                    warn(line, format!("cd into unlisted directory {:?}", path))?;
+                    root.get_dir_mut_or_create(current_path.as_slice());
+                }
+            }
+
+            Command::Ls { entries } => {
+                let current_dir = root.get_dir_mut(current_path.as_slice()).unwrap();
+
+                for entry in entries {
+                    match entry {
+                        LsEntry::Dir { line, name } if current_dir.files.contains_key(&name) => {
+                            warn(line, format!("{:?} listed as both file and dir", name))?;
+                        }
+                        LsEntry::Dir { name, .. } => {
+                            current_dir.subdirs.entry(name).or_insert_with(Dir::new);
+                        }
+                        LsEntry::File { line, name, .. }
+                            if current_dir.subdirs.contains_key(&name) =>
+                        {
+                            warn(line, format!("{:?} listed as both dir and file", name))?;
+                        }
+                        LsEntry::File { line, size, name } => {
+                            let old_size = current_dir.files.insert(name.clone(), size);
+                            if old_size.is_some_and(|old_size| old_size != size) {
+                                warn(line, format!("size of {:?} changed", name))?;
+                            }
+                        }
+                        LsEntry::Invalid { line, text } => {
+                            warn(line, format!("can't parse ls output {:?}", text))?;
+                        }
+                    }
+                }
+            }
+
+            Command::Unknown { text } => {
+                warn(line, format!("unknown command {:?}", text))?;
+            }
+        }
+    }
+
+    Ok((root, warnings))
+}
+
+fn main() {
+    let (root, warnings) = replay(io::stdin().lines().map(Result::unwrap), TOLERANT).unwrap();
+    for warning in warnings {
+        eprintln!("{}", warning);
+    }
+
+    println!("{}", root.render("/"));
+    println!();
+
+    for (path, size) in root.du("/") {
+        println!("{}\t{}", size, path);
+    }
+    println!();
+
+    let found = root.find("/", |e| {
+        e.name.contains(FIND_NAME) && e.size >= FIND_MIN_SIZE
+    });
+    for entry in found {
+        let kind = if entry.is_dir { "dir" } else { "file" };
+        println!("{}\t{}\t{}", entry.size, kind, entry.path);
+    }
+}
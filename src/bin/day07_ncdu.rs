@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::error;
+use std::io;
+use std::io::Read;
+use std::iter;
+use std::iter::Peekable;
+use std::process;
+use std::time::SystemTime;
+
+use serde_json::json;
+use serde_json::Value;
+
+const SIZE_THRESHOLD: usize = 100000;
+const USAGE_TARGET: usize = 70_000_000 - 30_000_000;
+
+#[derive(Debug)]
+enum LsEntry {
+    Dir { name: String },
+    File { size: usize, name: String },
+}
+
+#[derive(Debug)]
+enum Command {
+    Cd { path: String },
+    Ls { entries: Vec<LsEntry> },
+}
+
+impl Command {
+    fn read(output: &mut Peekable<impl Iterator<Item = String>>) -> Option<Self> {
+        let line = output.next()?;
+        let tokens: Vec<_> = line
+            .strip_prefix("$ ")
+            .unwrap()
+            .split_whitespace()
+            .collect();
+
+        match *tokens.as_slice() {
+            ["cd", path] => Some(Command::Cd {
+                path: path.to_owned(),
+            }),
+
+            ["ls"] => {
+                let mut nodes = vec![];
+                while let Some(line) = output.next_if(|l| !l.starts_with('$')) {
+                    let tokens: Vec<_> = line.split_whitespace().collect();
+                    let node = match *tokens.as_slice() {
+                        ["dir", name] => LsEntry::Dir {
+                            name: name.to_owned(),
+                        },
+                        [size, name] => LsEntry::File {
+                            size: size.parse().unwrap(),
+                            name: name.to_owned(),
+                        },
+                        _ => panic!(),
+                    };
+                    nodes.push(node);
+                }
+
+                Some(Command::Ls { entries: nodes })
+            }
+
+            _ => panic!(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Dir {
+    subdirs: HashMap<String, Dir>,
+    files: HashMap<String, usize>,
+    size: Option<usize>,
+}
+
+impl Dir {
+    fn new() -> Self {
+        Self {
+            subdirs: HashMap::new(),
+            files: HashMap::new(),
+            size: None,
+        }
+    }
+
+    fn get_dir_mut(&mut self, path: &[String]) -> Option<&mut Dir> {
+        match path {
+            [] => Some(self),
+            [first, rest @ ..] => self.subdirs.get_mut(first)?.get_dir_mut(rest),
+        }
+    }
+
+    fn update_size(&mut self) -> usize {
+        let subdirs_size: usize = self.subdirs.values_mut().map(Dir::update_size).sum();
+        let files_size: usize = self.files.values().sum();
+        let size = subdirs_size + files_size;
+        self.size = Some(size);
+        size
+    }
+
+    fn sum_of_small(&self) -> usize {
+        let size = self.size.unwrap();
+        let for_self = if size <= SIZE_THRESHOLD { size } else { 0 };
+        for_self + self.subdirs.values().map(Dir::sum_of_small).sum::<usize>()
+    }
+
+    fn best_deletion_size(&self, target: usize) -> Option<usize> {
+        assert!(self.size.is_some());
+        let for_self = self.size.filter(|s| *s >= target);
+        let for_subdirs = self.subdirs.values().map(|d| d.best_deletion_size(target));
+        iter::once(for_self).chain(for_subdirs).flatten().min()
+    }
+
+    // A directory in ncdu's export format is an array of its own info followed by its entries,
+    // where files are objects and subdirectories are nested arrays.
+    fn to_ncdu(&self, name: &str) -> Value {
+        let mut names: Vec<_> = self.files.keys().collect();
+        names.sort();
+        let mut subdir_names: Vec<_> = self.subdirs.keys().collect();
+        subdir_names.sort();
+
+        let info = iter::once(json!({ "name": name }));
+        let files = names.into_iter().map(
+            |name| json!({ "name": name, "asize": self.files[name], "dsize": self.files[name] }),
+        );
+        let subdirs = subdir_names
+            .into_iter()
+            .map(|name| self.subdirs[name].to_ncdu(name));
+
+        Value::Array(info.chain(files).chain(subdirs).collect())
+    }
+
+    fn from_ncdu(value: &Value) -> Result<(String, Dir), String> {
+        let Some((info, entries)) = value.as_array().and_then(|a| a.split_first()) else {
+            return Err(format!("expected a directory array, found {}", value));
+        };
+        let name = entry_name(info)?;
+
+        let mut dir = Dir::new();
+        for entry in entries {
+            if entry.is_array() {
+                let (subname, subdir) = Dir::from_ncdu(entry)?;
+                dir.subdirs.insert(subname, subdir);
+            } else {
+                // Apparent size is what a transcript's ls shows, so prefer it to disk usage
+                let size = ["asize", "dsize"]
+                    .iter()
+                    .find_map(|key| entry.get(key)?.as_u64())
+                    .unwrap_or(0);
+                dir.files.insert(entry_name(entry)?, size as usize);
+            }
+        }
+
+        Ok((name, dir))
+    }
+}
+
+fn entry_name(entry: &Value) -> Result<String, String> {
+    entry
+        .get("name")
+        .and_then(Value::as_str)
+        .map(str::to_owned)
+        .ok_or_else(|| format!("entry without a name: {}", entry))
+}
+
+fn export_ncdu(root: &Dir) -> Value {
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let metadata = json!({
+        "progname": env!("CARGO_PKG_NAME"),
+        "progver": env!("CARGO_PKG_VERSION"),
+        "timestamp": timestamp,
+    });
+    json!([1, 2, metadata, root.to_ncdu("/")])
+}
+
+fn import_ncdu(value: &Value) -> Result<Dir, String> {
+    match value.as_array().map(Vec::as_slice) {
+        Some([major, _minor, _metadata, root]) if major == 1 => Ok(Dir::from_ncdu(root)?.1),
+        _ => Err("not an ncdu export".to_owned()),
+    }
+}
+
+fn replay(output: &mut Peekable<impl Iterator<Item = String>>) -> Dir {
+    let first_command = Command::read(output).unwrap();
+    assert!(matches!(first_command, Command::Cd { path } if path == "/"));
+
+    let mut root = Dir::new();
+
+    let mut current_path: Vec<String> = vec![];
+
+    while let Some(command) = Command::read(output) {
+        match command {
+            Command::Cd { path } if path == ".." => {
+                assert!(current_path.pop().is_some());
+            }
+
+            Command::Cd { path } => {
+                current_path.push(path);
+                assert!(root.get_dir_mut(current_path.as_slice()).is_some());
+            }
+
+            Command::Ls { entries } => {
+                let current_dir = root.get_dir_mut(current_path.as_slice()).unwrap();
+
+                for entry in entries {
+                    match entry {
+                        LsEntry::Dir { name } => {
+                            assert!(current_dir.subdirs.insert(name, Dir::new()).is_none());
+                        }
+                        LsEntry::File { size, name } => {
+                            assert!(current_dir.files.insert(name, size).is_none());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    root
+}
+
+// Transcripts are exported as ncdu JSON, and ncdu JSON is imported and analysed like a transcript.
+fn run() -> Result<(), Box<dyn error::Error>> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+
+    if !input.trim_start().starts_with('[') {
+        let mut output = input.lines().map(str::to_owned).peekable();
+        let root = replay(&mut output);
+        println!("{}", export_ncdu(&root));
+        return Ok(());
+    }
+
+    let mut root = import_ncdu(&serde_json::from_str(&input)?)?;
+    root.update_size();
+
+    println!("{}", root.sum_of_small());
+
+    let deletion_target = root.size.unwrap().saturating_sub(USAGE_TARGET);
+    match root.best_deletion_size(deletion_target) {
+        Some(size) => println!("{}", size),
+        None => println!("nothing frees {}", deletion_target),
+    }
+
+    Ok(())
+}
+
+// Returning the error from main would only show its Debug form
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
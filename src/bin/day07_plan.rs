@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::io;
+use std::iter::Peekable;
+
+const USAGE_TARGET: usize = 70_000_000 - 30_000_000;
+
+#[derive(Debug)]
+enum LsEntry {
+    Dir { name: String },
+    File { size: usize, name: String },
+}
+
+#[derive(Debug)]
+enum Command {
+    Cd { path: String },
+    Ls { entries: Vec<LsEntry> },
+}
+
+impl Command {
+    fn read(output: &mut Peekable<impl Iterator<Item = String>>) -> Option<Self> {
+        let line = output.next()?;
+        let tokens: Vec<_> = line
+            .strip_prefix("$ ")
+            .unwrap()
+            .split_whitespace()
+            .collect();
+
+        match *tokens.as_slice() {
+            ["cd", path] => Some(Command::Cd {
+                path: path.to_owned(),
+            }),
+
+            ["ls"] => {
+                let mut nodes = vec![];
+                while let Some(line) = output.next_if(|l| !l.starts_with('$')) {
+                    let tokens: Vec<_> = line.split_whitespace().collect();
+                    let node = match *tokens.as_slice() {
+                        ["dir", name] => LsEntry::Dir {
+                            name: name.to_owned(),
+                        },
+                        [size, name] => LsEntry::File {
+                            size: size.parse().unwrap(),
+                            name: name.to_owned(),
+                        },
+                        _ => panic!(),
+                    };
+                    nodes.push(node);
+                }
+
+                Some(Command::Ls { entries: nodes })
+            }
+
+            _ => panic!(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Dir {
+    subdirs: HashMap<String, Dir>,
+    files: HashMap<String, usize>,
+    size: Option<usize>,
+}
+
+impl Dir {
+    fn new() -> Self {
+        Self {
+            subdirs: HashMap::new(),
+            files: HashMap::new(),
+            size: None,
+        }
+    }
+
+    fn get_dir_mut(&mut self, path: &[String]) -> Option<&mut Dir> {
+        match path {
+            [] => Some(self),
+            [first, rest @ ..] => self.subdirs.get_mut(first)?.get_dir_mut(rest),
+        }
+    }
+
+    fn update_size(&mut self) -> usize {
+        let subdirs_size: usize = self.subdirs.values_mut().map(Dir::update_size).sum();
+        let files_size: usize = self.files.values().sum();
+        let size = subdirs_size + files_size;
+        self.size = Some(size);
+        size
+    }
+
+    // Lists every directory below this one in pre-order, so each subtree is a contiguous range
+    fn flatten(&self, path: &str, candidates: &mut Vec<Candidate>) {
+        let mut names: Vec<_> = self.subdirs.keys().collect();
+        names.sort();
+
+        for name in names {
+            let subdir = &self.subdirs[name];
+            let i = candidates.len();
+            candidates.push(Candidate {
+                path: format!("{}/{}", path, name),
+                size: subdir.size.unwrap(),
+                subtree_end: 0,
+            });
+            subdir.flatten(&candidates[i].path.clone(), candidates);
+            candidates[i].subtree_end = candidates.len();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Candidate {
+    path: String,
+    size: usize,
+    subtree_end: usize,
+}
+
+#[derive(Debug)]
+struct Plan {
+    dirs: Vec<usize>,
+    freed: usize,
+}
+
+// Set of byte totals below some limit, one bit per total
+struct Sums {
+    words: Vec<u64>,
+    limit: usize,
+}
+
+impl Sums {
+    fn new(limit: usize) -> Self {
+        Self {
+            words: vec![0; limit.div_ceil(64)],
+            limit,
+        }
+    }
+
+    fn insert(&mut self, s: usize) {
+        self.words[s / 64] |= 1 << (s % 64);
+    }
+
+    // Adds every total in `other`, calling `on_new` for each one that wasn't already here
+    fn merge(&mut self, other: &Sums, mut on_new: impl FnMut(usize)) {
+        for (i, (word, other_word)) in self.words.iter_mut().zip(&other.words).enumerate() {
+            let mut new = other_word & !*word;
+            while new != 0 {
+                on_new(i * 64 + new.trailing_zeros() as usize);
+                new &= new - 1;
+            }
+            *word |= other_word;
+        }
+    }
+
+    // Adds `shift` to every total in `other` and adds those still below the limit
+    fn or_shifted(&mut self, other: &Sums, shift: usize) {
+        let (word_shift, bit_shift) = (shift / 64, shift % 64);
+        for i in word_shift..self.words.len() {
+            let j = i - word_shift;
+            let mut word = other.words[j] << bit_shift;
+            if bit_shift > 0 && j > 0 {
+                word |= other.words[j - 1] >> (64 - bit_shift);
+            }
+            self.words[i] |= word;
+        }
+
+        if !self.limit.is_multiple_of(64) {
+            *self.words.last_mut().unwrap() &= (1 << (self.limit % 64)) - 1;
+        }
+    }
+
+    fn first_at_least(&self, s: usize) -> Option<usize> {
+        if s >= self.limit {
+            return None;
+        }
+
+        let mut i = s / 64;
+        let mut word = self.words[i] & (u64::MAX << (s % 64));
+        loop {
+            if word != 0 {
+                return Some(i * 64 + word.trailing_zeros() as usize);
+            }
+            i += 1;
+            word = *self.words.get(i)?;
+        }
+    }
+}
+
+// Finds the set of non-nested directories that frees at least `target` bytes while freeing as
+// few bytes as possible.
+//
+// This is a knapsack over the candidates in pre-order: each one is either deleted, skipping its
+// subtree, or kept. A total reachable at one position is still reachable at the next, so
+// remembering where each total was first reached is enough to rebuild the plan. That takes a u32
+// for every total below the target, which is the bulk of the memory.
+fn plan(candidates: &[Candidate], target: usize) -> Option<Plan> {
+    if target == 0 {
+        return Some(Plan {
+            dirs: vec![],
+            freed: 0,
+        });
+    }
+
+    let mut ends: Vec<Vec<usize>> = vec![vec![]; candidates.len() + 1];
+    for (i, c) in candidates.iter().enumerate() {
+        ends[c.subtree_end].push(i);
+    }
+
+    let mut reached = Sums::new(target);
+    let mut pending: HashMap<usize, Sums> = HashMap::new();
+    let mut first_reached: Vec<u32> = vec![u32::MAX; target];
+
+    reached.insert(0);
+    first_reached[0] = 0;
+
+    // Freed bytes, the last directory deleted, and the total before deleting it
+    let mut best: Option<(usize, usize, usize)> = None;
+
+    for (i, c) in candidates.iter().enumerate() {
+        if let Some(sums) = pending.remove(&i) {
+            reached.merge(&sums, |s| first_reached[s] = i as u32);
+        }
+
+        if c.size == 0 {
+            continue;
+        }
+
+        if let Some(s) = reached.first_at_least(target.saturating_sub(c.size)) {
+            let freed = s + c.size;
+            if best.is_none_or(|(best_freed, ..)| freed < best_freed) {
+                best = Some((freed, i, s));
+            }
+        }
+
+        pending
+            .entry(c.subtree_end)
+            .or_insert_with(|| Sums::new(target))
+            .or_shifted(&reached, c.size);
+    }
+
+    let (freed, last, mut s) = best?;
+    let mut dirs = vec![last];
+
+    while s > 0 {
+        let reached_at = first_reached[s] as usize;
+        let k = ends[reached_at]
+            .iter()
+            .copied()
+            .find(|&k| {
+                let size = candidates[k].size;
+                size > 0 && size <= s && first_reached[s - size] as usize <= k
+            })
+            .unwrap();
+
+        dirs.push(k);
+        s -= candidates[k].size;
+    }
+    dirs.reverse();
+
+    Some(Plan { dirs, freed })
+}
+
+// Deleting the root alone always frees enough, so no plan needs more than one directory: the
+// smallest that frees enough by itself
+fn plan_fewest_dirs(candidates: &[Candidate], target: usize) -> Option<Plan> {
+    if target == 0 {
+        return Some(Plan {
+            dirs: vec![],
+            freed: 0,
+        });
+    }
+
+    let (i, c) = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.size >= target)
+        .min_by_key(|(_, c)| c.size)?;
+
+    Some(Plan {
+        dirs: vec![i],
+        freed: c.size,
+    })
+}
+
+fn main() {
+    let mut output = io::stdin().lines().map(Result::unwrap).peekable();
+
+    let first_command = Command::read(&mut output).unwrap();
+    assert!(matches!(first_command, Command::Cd { path } if path == "/"));
+
+    let mut root = Dir::new();
+
+    let mut current_path: Vec<String> = vec![];
+
+    while let Some(command) = Command::read(&mut output) {
+        match command {
+            Command::Cd { path } if path == ".." => {
+                assert!(current_path.pop().is_some());
+            }
+
+            Command::Cd { path } => {
+                current_path.push(path);
+                assert!(root.get_dir_mut(current_path.as_slice()).is_some());
+            }
+
+            Command::Ls { entries } => {
+                let current_dir = root.get_dir_mut(current_path.as_slice()).unwrap();
+
+                for entry in entries {
+                    match entry {
+                        LsEntry::Dir { name } => {
+                            assert!(current_dir.subdirs.insert(name, Dir::new()).is_none());
+                        }
+                        LsEntry::File { size, name } => {
+                            assert!(current_dir.files.insert(name, size).is_none());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    root.update_size();
+    let deletion_target = root.size.unwrap().saturating_sub(USAGE_TARGET);
+
+    // The root is a candidate too, so there's always a plan
+    let mut candidates = vec![Candidate {
+        path: "/".to_owned(),
+        size: root.size.unwrap(),
+        subtree_end: 0,
+    }];
+    root.flatten("", &mut candidates);
+    candidates[0].subtree_end = candidates.len();
+
+    let fewest_bytes = plan(&candidates, deletion_target);
+    let fewest_dirs = plan_fewest_dirs(&candidates, deletion_target);
+
+    for (name, plan) in [("fewest bytes", fewest_bytes), ("fewest dirs", fewest_dirs)] {
+        match plan {
+            Some(plan) => {
+                println!("{}: {} in {} dirs", name, plan.freed, plan.dirs.len());
+                for i in plan.dirs {
+                    println!("{}\t{}", candidates[i].size, candidates[i].path);
+                }
+            }
+            None => println!("{}: nothing frees {}", name, deletion_target),
+        }
+    }
+}
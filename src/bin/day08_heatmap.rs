@@ -0,0 +1,279 @@
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+
+const VISIBLE_PATH: &str = "day08_visible.pgm";
+const SCORES_PATH: &str = "day08_scores.ppm";
+const RAMP: Ramp = Ramp::Heat;
+
+// Scenic scores span several orders of magnitude, so spread them out on a log scale
+const LOG_SCALE: bool = true;
+
+// Half the length of each arm of the cross marking the best treehouse
+const MARKER_RADIUS: isize = 3;
+
+struct Matrix<T> {
+    vec: Vec<T>,
+    width: usize,
+}
+
+impl<T> Matrix<T> {
+    fn get(&self, y: isize, x: isize) -> Option<&T> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        let x: usize = x.try_into().ok().unwrap();
+        let y: usize = y.try_into().ok().unwrap();
+
+        if x >= self.width {
+            return None;
+        }
+
+        self.vec.get((y * self.width) + x)
+    }
+
+    fn set(&mut self, y: isize, x: isize, value: T) {
+        assert!(x >= 0 && y >= 0);
+
+        let x: usize = x.try_into().ok().unwrap();
+        let y: usize = y.try_into().ok().unwrap();
+
+        assert!(x < self.width);
+
+        self.vec[(y * self.width) + x] = value;
+    }
+
+    fn height(&self) -> usize {
+        self.vec.len() / self.width
+    }
+
+    fn map<U>(&self, f: impl Fn(&T) -> U) -> Matrix<U> {
+        Matrix {
+            vec: self.vec.iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    fn filled(width: usize, height: usize, value: T) -> Self {
+        Matrix {
+            vec: vec![value; width * height],
+            width,
+        }
+    }
+}
+
+fn sweep_line(
+    trees: &Matrix<u8>,
+    line: impl Iterator<Item = (isize, isize)>,
+    visible: &mut Matrix<bool>,
+    scores: &mut Matrix<usize>,
+) {
+    let mut stack: Vec<(u8, usize)> = vec![];
+
+    for (i, (y, x)) in line.enumerate() {
+        let tree = *trees.get(y, x).unwrap();
+
+        while stack.last().is_some_and(|&(blocker, _)| blocker < tree) {
+            stack.pop();
+        }
+
+        let viewing_distance = match stack.last() {
+            Some(&(_, j)) => i - j,
+            None => {
+                visible.set(y, x, true);
+                i
+            }
+        };
+        let score = *scores.get(y, x).unwrap();
+        scores.set(y, x, score * viewing_distance);
+
+        stack.push((tree, i));
+    }
+}
+
+fn visibility_and_scores(trees: &Matrix<u8>) -> (Matrix<bool>, Matrix<usize>) {
+    let width = trees.width as isize;
+    let height = trees.height() as isize;
+
+    let mut visible = Matrix::filled(trees.width, trees.height(), false);
+    let mut scores = Matrix::filled(trees.width, trees.height(), 1);
+
+    for y in 0..height {
+        let line = (0..width).map(|x| (y, x));
+        sweep_line(trees, line, &mut visible, &mut scores);
+        let line = (0..width).rev().map(|x| (y, x));
+        sweep_line(trees, line, &mut visible, &mut scores);
+    }
+    for x in 0..width {
+        let line = (0..height).map(|y| (y, x));
+        sweep_line(trees, line, &mut visible, &mut scores);
+        let line = (0..height).rev().map(|y| (y, x));
+        sweep_line(trees, line, &mut visible, &mut scores);
+    }
+
+    (visible, scores)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Ramp {
+    Gray,
+    Heat,
+    Viridis,
+}
+
+impl Ramp {
+    const ALL: [Ramp; 3] = [Ramp::Gray, Ramp::Heat, Ramp::Viridis];
+
+    // Evenly spaced colours, interpolated linearly in between
+    const fn stops(self) -> &'static [[u8; 3]] {
+        match self {
+            Ramp::Gray => &[[0, 0, 0], [255, 255, 255]],
+            Ramp::Heat => &[
+                [0, 0, 0],
+                [128, 0, 0],
+                [255, 64, 0],
+                [255, 192, 0],
+                [255, 255, 255],
+            ],
+            Ramp::Viridis => &[
+                [68, 1, 84],
+                [59, 82, 139],
+                [33, 145, 140],
+                [94, 201, 98],
+                [253, 231, 37],
+            ],
+        }
+    }
+
+    // A colour that stands out against the whole ramp, for marking points
+    const fn marker(self) -> [u8; 3] {
+        match self {
+            Ramp::Gray => [255, 0, 0],
+            Ramp::Heat => [0, 255, 255],
+            Ramp::Viridis => [255, 0, 255],
+        }
+    }
+
+    // Maps `t` in 0..=1 to a colour
+    fn color(self, t: f64) -> [u8; 3] {
+        let stops = self.stops();
+        let pos = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let i = Ord::min(pos as usize, stops.len() - 2);
+        let frac = pos - i as f64;
+
+        let (a, b) = (stops[i], stops[i + 1]);
+        [0, 1, 2].map(|c| (a[c] as f64 + (b[c] as f64 - a[c] as f64) * frac).round() as u8)
+    }
+}
+
+// The best treehouse has to stand out whichever ramp is chosen
+const _: () = {
+    let mut i = 0;
+    while i < Ramp::ALL.len() {
+        let (stops, [r, g, b]) = (Ramp::ALL[i].stops(), Ramp::ALL[i].marker());
+        let mut j = 0;
+        while j < stops.len() {
+            let hidden = stops[j][0] == r && stops[j][1] == g && stops[j][2] == b;
+            assert!(!hidden, "a ramp's marker is one of its stops");
+            j += 1;
+        }
+        i += 1;
+    }
+};
+
+// Calls `f` on every cell of a cross centred on (y, x), clipped to the matrix
+fn for_each_marker_cell<T>(m: &Matrix<T>, (y, x): (isize, isize), mut f: impl FnMut(isize, isize)) {
+    for d in -MARKER_RADIUS..=MARKER_RADIUS {
+        for (my, mx) in [(y + d, x), (y, x + d)] {
+            if m.get(my, mx).is_some() {
+                f(my, mx);
+            }
+        }
+    }
+}
+
+// Binary PGM, one byte per pixel
+fn write_pgm(out: &mut impl Write, image: &Matrix<u8>) -> io::Result<()> {
+    write!(out, "P5\n{} {}\n255\n", image.width, image.height())?;
+    out.write_all(&image.vec)
+}
+
+// Binary PPM, three bytes per pixel
+fn write_ppm(out: &mut impl Write, image: &Matrix<[u8; 3]>) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", image.width, image.height())?;
+    for pixel in &image.vec {
+        out.write_all(pixel)?;
+    }
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let mut stdin = io::stdin().lock();
+
+    let mut first_line = String::new();
+    stdin.read_line(&mut first_line).unwrap();
+
+    let width = first_line.trim_end().len();
+
+    let trees = Matrix {
+        vec: first_line
+            .bytes()
+            .chain(stdin.bytes().map(Result::unwrap))
+            .filter(|b| !b.is_ascii_whitespace())
+            .map(|b| b - b'0')
+            .collect(),
+        width,
+    };
+
+    let height = trees.vec.len() / width;
+    assert_eq!(trees.vec.len(), width * height);
+
+    let (visible, scores) = visibility_and_scores(&trees);
+
+    let (best_index, &best_score) = scores
+        .vec
+        .iter()
+        .enumerate()
+        .max_by_key(|&(i, score)| (score, std::cmp::Reverse(i)))
+        .unwrap();
+    let best = ((best_index / width) as isize, (best_index % width) as isize);
+
+    let scale = |score: usize| match (LOG_SCALE, best_score) {
+        (_, 0) => 0.0,
+        (true, _) => (score as f64).ln_1p() / (best_score as f64).ln_1p(),
+        (false, _) => score as f64 / best_score as f64,
+    };
+
+    // Visible trees are white, with the best treehouse in mid-grey
+    let mut visible_image = visible.map(|&v| if v { 255 } else { 0 });
+    for_each_marker_cell(&visible, best, |y, x| visible_image.set(y, x, 128));
+    write_pgm(
+        &mut BufWriter::new(File::create(VISIBLE_PATH)?),
+        &visible_image,
+    )?;
+
+    let mut scores_image = scores.map(|&score| RAMP.color(scale(score)));
+    for_each_marker_cell(&scores, best, |y, x| scores_image.set(y, x, RAMP.marker()));
+    write_ppm(
+        &mut BufWriter::new(File::create(SCORES_PATH)?),
+        &scores_image,
+    )?;
+
+    println!(
+        "{} visible, written to {}",
+        visible.vec.iter().filter(|v| **v).count(),
+        VISIBLE_PATH
+    );
+    println!(
+        "best score {} at row {} column {}, written to {}",
+        best_score, best.0, best.1, SCORES_PATH
+    );
+
+    Ok(())
+}
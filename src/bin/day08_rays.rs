@@ -0,0 +1,183 @@
+use std::io;
+use std::io::BufRead;
+use std::io::Read;
+
+// Directions to look in, as steps from one tree to the next. Steps that skip over cells, like
+// knight moves, only consider the trees they land on.
+const DIRECTIONS: &[(isize, isize)] = &[
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+const BLOCKING_RULES: &[Blocking] = &[Blocking::TallerOrEqual, Blocking::StrictlyTaller];
+
+struct Matrix<T> {
+    vec: Vec<T>,
+    width: usize,
+}
+
+impl<T> Matrix<T> {
+    fn get(&self, y: isize, x: isize) -> Option<&T> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        let x: usize = x.try_into().ok().unwrap();
+        let y: usize = y.try_into().ok().unwrap();
+
+        if x >= self.width {
+            return None;
+        }
+
+        self.vec.get((y * self.width) + x)
+    }
+
+    fn set(&mut self, y: isize, x: isize, value: T) {
+        assert!(x >= 0 && y >= 0);
+
+        let x: usize = x.try_into().ok().unwrap();
+        let y: usize = y.try_into().ok().unwrap();
+
+        assert!(x < self.width);
+
+        self.vec[(y * self.width) + x] = value;
+    }
+
+    fn height(&self) -> usize {
+        self.vec.len() / self.width
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    fn filled(width: usize, height: usize, value: T) -> Self {
+        Matrix {
+            vec: vec![value; width * height],
+            width,
+        }
+    }
+}
+
+// Which trees in the way stop the view from a tree of the given height
+#[derive(Debug, Clone, Copy)]
+enum Blocking {
+    TallerOrEqual,
+    StrictlyTaller,
+}
+
+impl Blocking {
+    fn blocks(self, blocker: u8, tree: u8) -> bool {
+        match self {
+            Blocking::TallerOrEqual => blocker >= tree,
+            Blocking::StrictlyTaller => blocker > tree,
+        }
+    }
+}
+
+// Looks in direction (dy, dx) from every tree. The trees reached by repeatedly stepping in that
+// direction form chains, so each chain is walked backwards from its last tree, keeping a stack of
+// the trees that haven't been overshadowed yet.
+fn look(
+    trees: &Matrix<u8>,
+    (dy, dx): (isize, isize),
+    blocking: Blocking,
+    visible: &mut Matrix<bool>,
+    scores: &mut Matrix<u128>,
+) {
+    assert!((dy, dx) != (0, 0));
+
+    let width = trees.width as isize;
+    let height = trees.height() as isize;
+
+    let mut stack: Vec<(u8, usize)> = vec![];
+
+    let all_coords = (0..height).flat_map(|y| (0..width).map(move |x| (y, x)));
+    let chain_ends = all_coords.filter(|&(y, x)| trees.get(y + dy, x + dx).is_none());
+
+    for (end_y, end_x) in chain_ends {
+        stack.clear();
+
+        let chain = (0..).map_while(|i| {
+            let (y, x) = (end_y - (i * dy), end_x - (i * dx));
+            trees.get(y, x).map(|tree| (y, x, *tree))
+        });
+
+        for (i, (y, x, tree)) in chain.enumerate() {
+            while stack
+                .last()
+                .is_some_and(|&(blocker, _)| !blocking.blocks(blocker, tree))
+            {
+                stack.pop();
+            }
+
+            let viewing_distance = match stack.last() {
+                Some(&(_, j)) => i - j,
+                None => {
+                    visible.set(y, x, true);
+                    i
+                }
+            };
+            // A product of one distance per direction soon outgrows usize on large grids
+            let score = scores
+                .get(y, x)
+                .unwrap()
+                .checked_mul(viewing_distance as u128);
+            scores.set(y, x, score.expect("scenic score overflowed"));
+
+            stack.push((tree, i));
+        }
+    }
+}
+
+fn visibility_and_scores(
+    trees: &Matrix<u8>,
+    directions: &[(isize, isize)],
+    blocking: Blocking,
+) -> (Matrix<bool>, Matrix<u128>) {
+    let mut visible = Matrix::filled(trees.width, trees.height(), false);
+    let mut scores = Matrix::filled(trees.width, trees.height(), 1);
+
+    for &direction in directions {
+        look(trees, direction, blocking, &mut visible, &mut scores);
+    }
+
+    (visible, scores)
+}
+
+fn main() {
+    let mut stdin = io::stdin().lock();
+
+    let mut first_line = String::new();
+    stdin.read_line(&mut first_line).unwrap();
+
+    let width = first_line.trim_end().len();
+
+    let trees = Matrix {
+        vec: first_line
+            .bytes()
+            .chain(stdin.bytes().map(Result::unwrap))
+            .filter(|b| !b.is_ascii_whitespace())
+            .map(|b| b - b'0')
+            .collect(),
+        width,
+    };
+
+    let height = trees.vec.len() / width;
+    assert_eq!(trees.vec.len(), width * height);
+
+    for &blocking in BLOCKING_RULES {
+        let (visible, scores) = visibility_and_scores(&trees, DIRECTIONS, blocking);
+
+        println!(
+            "{:?}: {} visible, best score {}",
+            blocking,
+            visible.vec.iter().filter(|v| **v).count(),
+            scores.vec.iter().max().unwrap()
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::Debug;
+use std::io;
+use std::io::BufRead;
+use std::io::Read;
+
+struct Matrix<T> {
+    vec: Vec<T>,
+    width: usize,
+}
+
+impl<T> Matrix<T> {
+    fn get(&self, y: isize, x: isize) -> Option<&T> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        let x: usize = x.try_into().ok().unwrap();
+        let y: usize = y.try_into().ok().unwrap();
+
+        if x >= self.width {
+            return None;
+        }
+
+        self.vec.get((y * self.width) + x)
+    }
+
+    fn set(&mut self, y: isize, x: isize, value: T) {
+        assert!(x >= 0 && y >= 0);
+
+        let x: usize = x.try_into().ok().unwrap();
+        let y: usize = y.try_into().ok().unwrap();
+
+        assert!(x < self.width);
+
+        self.vec[(y * self.width) + x] = value;
+    }
+
+    fn height(&self) -> usize {
+        self.vec.len() / self.width
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    fn filled(width: usize, height: usize, value: T) -> Self {
+        Matrix {
+            vec: vec![value; width * height],
+            width,
+        }
+    }
+}
+
+impl<T: Debug> Debug for Matrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Matrix {{")?;
+        for row in self.vec.chunks_exact(self.width) {
+            write!(f, "    ")?;
+            for elem in row {
+                elem.fmt(f)?;
+                write!(f, " ")?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+// Walks one line of trees, looking back towards where the line started. A stack of the trees
+// not yet blocked by a taller one gives each tree's nearest blocker in amortised constant time.
+fn sweep_line(
+    trees: &Matrix<u8>,
+    line: impl Iterator<Item = (isize, isize)>,
+    visible: &mut Matrix<bool>,
+    scores: &mut Matrix<usize>,
+) {
+    let mut stack: Vec<(u8, usize)> = vec![];
+
+    for (i, (y, x)) in line.enumerate() {
+        let tree = *trees.get(y, x).unwrap();
+
+        while stack.last().is_some_and(|&(blocker, _)| blocker < tree) {
+            stack.pop();
+        }
+
+        let viewing_distance = match stack.last() {
+            Some(&(_, j)) => i - j,
+            None => {
+                visible.set(y, x, true);
+                i
+            }
+        };
+        let score = *scores.get(y, x).unwrap();
+        scores.set(y, x, score * viewing_distance);
+
+        stack.push((tree, i));
+    }
+}
+
+fn visibility_and_scores(trees: &Matrix<u8>) -> (Matrix<bool>, Matrix<usize>) {
+    let width = trees.width as isize;
+    let height = trees.height() as isize;
+
+    let mut visible = Matrix::filled(trees.width, trees.height(), false);
+    let mut scores = Matrix::filled(trees.width, trees.height(), 1);
+
+    for y in 0..height {
+        // Left to right
+        let line = (0..width).map(|x| (y, x));
+        sweep_line(trees, line, &mut visible, &mut scores);
+        // Right to left
+        let line = (0..width).rev().map(|x| (y, x));
+        sweep_line(trees, line, &mut visible, &mut scores);
+    }
+    for x in 0..width {
+        // Top to bottom
+        let line = (0..height).map(|y| (y, x));
+        sweep_line(trees, line, &mut visible, &mut scores);
+        // Bottom to top
+        let line = (0..height).rev().map(|y| (y, x));
+        sweep_line(trees, line, &mut visible, &mut scores);
+    }
+
+    (visible, scores)
+}
+
+fn main() {
+    let mut stdin = io::stdin().lock();
+
+    let mut first_line = String::new();
+    stdin.read_line(&mut first_line).unwrap();
+
+    let width = first_line.trim_end().len();
+
+    let trees = Matrix {
+        vec: first_line
+            .bytes()
+            .chain(stdin.bytes().map(Result::unwrap))
+            .filter(|b| !b.is_ascii_whitespace())
+            .map(|b| b - b'0')
+            .collect(),
+        width,
+    };
+
+    let height = trees.vec.len() / width;
+    assert_eq!(trees.vec.len(), width * height);
+
+    let (visible, scores) = visibility_and_scores(&trees);
+
+    println!("{}", visible.vec.iter().filter(|v| **v).count());
+    println!("{}", scores.vec.iter().max().unwrap());
+}
@@ -0,0 +1,132 @@
+use std::array;
+use std::collections::HashSet;
+use std::io;
+use std::ops;
+use std::str::FromStr;
+
+const DIMENSIONS: usize = 3;
+const NUM_KNOTS: usize = 10;
+
+// Letters for moving along the first few axes, positive direction first. Any axis can also be
+// moved along with `+k` or `-k`, counting axes from 0.
+const AXIS_NAMES: &[(&str, &str)] = &[("R", "L"), ("U", "D"), ("F", "B")];
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Point<const N: usize>([isize; N]);
+
+impl<const N: usize> Point<N> {
+    fn origin() -> Self {
+        Self([0; N])
+    }
+
+    fn unit(axis: usize, sign: isize) -> Self {
+        Self(array::from_fn(|i| if i == axis { sign } else { 0 }))
+    }
+
+    fn abs_chebyshev(self) -> isize {
+        self.0.iter().map(|c| c.abs()).max().unwrap_or(0)
+    }
+
+    fn clamp_chebyshev(self, d: isize) -> Self {
+        Self(self.0.map(|c| c.clamp(-d, d)))
+    }
+}
+
+impl<const N: usize> ops::Add for Point<N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<const N: usize> ops::AddAssign for Point<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> ops::Sub for Point<N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Motion<const N: usize> {
+    dir: Point<N>,
+    dist: usize,
+}
+
+impl<const N: usize> FromStr for Motion<N> {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_whitespace().collect();
+        let [name, dist] = *tokens.as_slice() else {
+            return Err(());
+        };
+
+        let named = AXIS_NAMES
+            .iter()
+            .enumerate()
+            .find_map(|(axis, (pos, neg))| {
+                [(pos, 1), (neg, -1)]
+                    .into_iter()
+                    .find(|(n, _)| **n == name)
+                    .map(|(_, sign)| (axis, sign))
+            });
+        let numbered = || {
+            let (sign, axis) = match name.split_at_checked(1)? {
+                ("+", axis) => (1, axis),
+                ("-", axis) => (-1, axis),
+                _ => return None,
+            };
+            Some((axis.parse().ok()?, sign))
+        };
+
+        let (axis, sign) = named.or_else(numbered).ok_or(())?;
+        if axis >= N {
+            return Err(());
+        }
+
+        Ok(Motion {
+            dir: Point::unit(axis, sign),
+            dist: dist.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+fn main() {
+    let mut knots = [Point::<DIMENSIONS>::origin(); NUM_KNOTS];
+    let mut visited: Vec<HashSet<Point<DIMENSIONS>>> =
+        vec![HashSet::from([Point::origin()]); NUM_KNOTS];
+
+    for (line_no, line) in io::stdin().lines().map(Result::unwrap).enumerate() {
+        let motion: Motion<DIMENSIONS> = line.parse().unwrap_or_else(|_| {
+            panic!(
+                "line {}: can't parse {:?} in {}D",
+                line_no + 1,
+                line,
+                DIMENSIONS
+            )
+        });
+
+        for _ in 0..motion.dist {
+            knots[0] += motion.dir;
+            visited[0].insert(knots[0]);
+
+            for i in 0..NUM_KNOTS - 1 {
+                let d = knots[i] - knots[i + 1];
+                if d.abs_chebyshev() > 1 {
+                    knots[i + 1] += d.clamp_chebyshev(1);
+                    visited[i + 1].insert(knots[i + 1]);
+                }
+            }
+        }
+    }
+
+    for (knot, cells) in visited.iter().enumerate() {
+        println!("{}: {} cells", knot, cells.len());
+    }
+}
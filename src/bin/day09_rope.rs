@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+use std::io;
+use std::ops;
+use std::str::FromStr;
+
+const NUM_KNOTS: usize = 10;
+
+// Print the whole rope after every step, like the diagrams in the puzzle
+const RENDER_STEPS: bool = false;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Point(isize, isize);
+
+impl Point {
+    fn abs_chebyshev(self) -> isize {
+        Ord::max(self.0.abs(), self.1.abs())
+    }
+
+    fn clamp_chebyshev(self, d: isize) -> Self {
+        Self(self.0.clamp(-d, d), self.1.clamp(-d, d))
+    }
+}
+
+impl ops::Add for Point {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl ops::AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl ops::Sub for Point {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Motion {
+    name: char,
+    dir: Point,
+    dist: usize,
+}
+
+impl FromStr for Motion {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_whitespace().collect();
+        let [name, dist] = *tokens.as_slice() else {
+            return Err(());
+        };
+        let dir = match name {
+            "U" => Point(0, 1),
+            "D" => Point(0, -1),
+            "L" => Point(-1, 0),
+            "R" => Point(1, 0),
+            _ => return Err(()),
+        };
+        Ok(Motion {
+            name: name.chars().next().unwrap(),
+            dir,
+            dist: dist.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+// Smallest rectangle containing a set of points
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    min: Point,
+    max: Point,
+}
+
+impl Bounds {
+    fn around(points: impl Iterator<Item = Point>) -> Self {
+        let mut bounds = Bounds {
+            min: Point(0, 0),
+            max: Point(0, 0),
+        };
+        for p in points {
+            bounds.min = Point(Ord::min(bounds.min.0, p.0), Ord::min(bounds.min.1, p.1));
+            bounds.max = Point(Ord::max(bounds.max.0, p.0), Ord::max(bounds.max.1, p.1));
+        }
+        bounds
+    }
+
+    // Renders one character per point, with y increasing upwards
+    fn render(self, cell: impl Fn(Point) -> char) -> String {
+        let rows = (self.min.1..=self.max.1).rev().map(|y| {
+            (self.min.0..=self.max.0)
+                .map(|x| cell(Point(x, y)))
+                .collect::<String>()
+        });
+        rows.collect::<Vec<_>>().join("\n")
+    }
+}
+
+// Knots are labelled as in the puzzle, with `*` for any beyond the ninth
+fn label(knot: usize, num_knots: usize) -> char {
+    match (knot, num_knots) {
+        (0, _) => 'H',
+        (1, 2) => 'T',
+        (k, _) => char::from_digit(k as u32, 10).unwrap_or('*'),
+    }
+}
+
+// Draws the knots, with ones nearer the head covering the rest, and `s` for the start
+fn render_knots(knots: &[Point], bounds: Bounds) -> String {
+    bounds.render(|p| match knots.iter().position(|k| *k == p) {
+        Some(knot) => label(knot, knots.len()),
+        None if p == Point(0, 0) => 's',
+        None => '.',
+    })
+}
+
+#[derive(Debug)]
+struct Rope {
+    knots: Vec<Point>,
+    // Every position each knot has been in, including the start
+    visited: Vec<HashSet<Point>>,
+}
+
+impl Rope {
+    fn new(num_knots: usize) -> Self {
+        assert!(num_knots > 0);
+        Rope {
+            knots: vec![Point(0, 0); num_knots],
+            visited: vec![HashSet::from([Point(0, 0)]); num_knots],
+        }
+    }
+
+    fn step(&mut self, dir: Point) {
+        self.knots[0] += dir;
+        self.visited[0].insert(self.knots[0]);
+
+        for i in 0..self.knots.len() - 1 {
+            let d = self.knots[i] - self.knots[i + 1];
+            if d.abs_chebyshev() <= 1 {
+                // Nothing further down the rope will move either
+                break;
+            }
+            self.knots[i + 1] += d.clamp_chebyshev(1);
+            self.visited[i + 1].insert(self.knots[i + 1]);
+        }
+    }
+
+    // Applies the motions one step at a time, yielding the state after each step
+    fn states<I: Iterator<Item = Motion>>(&mut self, motions: I) -> States<'_, I> {
+        States {
+            rope: self,
+            motions,
+            current: None,
+        }
+    }
+
+    // Bounds of everywhere any knot has been
+    fn bounds(&self) -> Bounds {
+        Bounds::around(self.visited.iter().flatten().copied())
+    }
+
+    fn render_visited(&self, knot: usize, bounds: Bounds) -> String {
+        bounds.render(|p| match p {
+            Point(0, 0) => 's',
+            p if self.visited[knot].contains(&p) => '#',
+            _ => '.',
+        })
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    motion: Motion,
+    // Steps of the current motion taken so far, starting at 1
+    step: usize,
+    knots: Vec<Point>,
+}
+
+struct States<'a, I> {
+    rope: &'a mut Rope,
+    motions: I,
+    current: Option<(Motion, usize)>,
+}
+
+impl<I: Iterator<Item = Motion>> Iterator for States<'_, I> {
+    type Item = State;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (motion, step) = loop {
+            match self.current {
+                Some((motion, step)) if step < motion.dist => break (motion, step + 1),
+                _ => self.current = Some((self.motions.next()?, 0)),
+            }
+        };
+        self.current = Some((motion, step));
+
+        self.rope.step(motion.dir);
+
+        Some(State {
+            motion,
+            step,
+            knots: self.rope.knots.clone(),
+        })
+    }
+}
+
+fn main() {
+    let motions: Vec<Motion> = io::stdin()
+        .lines()
+        .map(|line| line.unwrap().parse().unwrap())
+        .collect();
+
+    let mut rope = Rope::new(NUM_KNOTS);
+    rope.states(motions.iter().copied()).for_each(drop);
+    let bounds = rope.bounds();
+
+    // Replay with the final bounds, so every diagram has the same frame
+    if RENDER_STEPS {
+        let mut replay = Rope::new(NUM_KNOTS);
+        let initial = render_knots(&replay.knots, bounds);
+        println!("== Initial State ==\n\n{}\n", initial);
+
+        for state in replay.states(motions.iter().copied()) {
+            let Motion { name, dist, .. } = state.motion;
+            println!("== {} {} ({}/{}) ==\n", name, dist, state.step, dist);
+            println!("{}\n", render_knots(&state.knots, bounds));
+        }
+    }
+
+    for (knot, visited) in rope.visited.iter().enumerate() {
+        println!("{}: {} positions", label(knot, NUM_KNOTS), visited.len());
+    }
+    println!();
+
+    println!("{}", rope.render_visited(NUM_KNOTS - 1, bounds));
+}
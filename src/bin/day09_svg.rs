@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::ops;
+
+const NUM_KNOTS: usize = 10;
+const SVG_PATH: &str = "day09_trails.svg";
+
+// Pixels per grid cell, shrunk if the picture would be wider or taller than MAX_SIZE
+const CELL_SIZE: f64 = 8.0;
+const MAX_SIZE: f64 = 4000.0;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Point(isize, isize);
+
+impl Point {
+    fn abs_chebyshev(self) -> isize {
+        Ord::max(self.0.abs(), self.1.abs())
+    }
+
+    fn clamp_chebyshev(self, d: isize) -> Self {
+        Self(self.0.clamp(-d, d), self.1.clamp(-d, d))
+    }
+
+    fn signum(self) -> Self {
+        Self(self.0.signum(), self.1.signum())
+    }
+}
+
+impl ops::Add for Point {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl ops::AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl ops::Sub for Point {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+// A knot's path, keeping only the points where it turns. Long straight runs are common, so this
+// keeps the SVG far smaller than one vertex per step.
+#[derive(Debug)]
+struct Trail {
+    points: Vec<Point>,
+}
+
+impl Trail {
+    fn new() -> Self {
+        Trail {
+            points: vec![Point(0, 0)],
+        }
+    }
+
+    fn push(&mut self, p: Point) {
+        match *self.points.as_slice() {
+            [.., last] if last == p => {}
+            // The last segment may span many steps, so compare directions rather than steps
+            [.., prev, last] if (p - last).signum() == (last - prev).signum() => {
+                *self.points.last_mut().unwrap() = p;
+            }
+            _ => self.points.push(p),
+        }
+    }
+}
+
+// Knots are coloured around the hue circle, from red at the head
+fn knot_color(knot: usize, num_knots: usize) -> String {
+    let hue = 360 * knot / num_knots;
+    format!("hsl({}, 80%, 45%)", hue)
+}
+
+// SVG's y axis points down, and the puzzle's points up
+fn write_point(out: &mut impl Write, p: Point) -> io::Result<()> {
+    write!(out, "{},{} ", p.0, -p.1)
+}
+
+fn write_svg(
+    out: &mut impl Write,
+    trails: &[Trail],
+    tail_visited: &HashSet<Point>,
+) -> io::Result<()> {
+    let all_points = trails.iter().flat_map(|t| &t.points);
+    let min_x = all_points.clone().map(|p| p.0).min().unwrap();
+    let max_x = all_points.clone().map(|p| p.0).max().unwrap();
+    let min_y = all_points.clone().map(|p| p.1).min().unwrap();
+    let max_y = all_points.map(|p| p.1).max().unwrap();
+
+    // One cell of margin around everything
+    let (width, height) = (max_x - min_x + 2, max_y - min_y + 2);
+    let scale = f64::min(CELL_SIZE, MAX_SIZE / Ord::max(width, height) as f64);
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{:.0}" height="{:.0}">"#,
+        min_x as f64 - 1.0,
+        -max_y as f64 - 1.0,
+        width,
+        height,
+        width as f64 * scale,
+        height as f64 * scale,
+    )?;
+    writeln!(
+        out,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="white"/>"#,
+        min_x - 1,
+        -max_y - 1,
+        width,
+        height
+    )?;
+
+    // Shade the tail's cells as one path, merging each row into horizontal runs
+    let mut rows: BTreeMap<isize, Vec<isize>> = BTreeMap::new();
+    for p in tail_visited {
+        rows.entry(-p.1).or_default().push(p.0);
+    }
+    write!(
+        out,
+        r#"<path fill="{}" fill-opacity="0.25" d=""#,
+        knot_color(trails.len() - 1, trails.len())
+    )?;
+    for (y, xs) in &mut rows {
+        xs.sort_unstable();
+        for run in xs.chunk_by(|a, b| b - a == 1) {
+            let x = run[0] as f64 - 0.5;
+            write!(
+                out,
+                "M{},{}h{}v1h-{}z",
+                x,
+                *y as f64 - 0.5,
+                run.len(),
+                run.len()
+            )?;
+        }
+    }
+    writeln!(out, r#""/>"#)?;
+
+    // Tail first, so the knots nearer the head are drawn on top
+    for (knot, trail) in trails.iter().enumerate().rev() {
+        write!(
+            out,
+            r#"<polyline fill="none" stroke="{}" stroke-width="0.15" stroke-linejoin="round" points=""#,
+            knot_color(knot, trails.len())
+        )?;
+        for &p in &trail.points {
+            write_point(out, p)?;
+        }
+        writeln!(out, r#""/>"#)?;
+    }
+
+    writeln!(out, r#"<circle cx="0" cy="0" r="0.3" fill="black"/>"#)?;
+    writeln!(out, "</svg>")
+}
+
+fn main() -> io::Result<()> {
+    let mut knots = [Point(0, 0); NUM_KNOTS];
+    let mut trails: Vec<Trail> = (0..NUM_KNOTS).map(|_| Trail::new()).collect();
+    let mut tail_visited: HashSet<Point> = HashSet::from([Point(0, 0)]);
+    let mut steps = 0;
+
+    for line in io::stdin().lines().map(Result::unwrap) {
+        let tokens: Vec<_> = line.split_whitespace().collect();
+        assert_eq!(tokens.len(), 2);
+        let dir = match tokens[0] {
+            "U" => Point(0, 1),
+            "D" => Point(0, -1),
+            "L" => Point(-1, 0),
+            "R" => Point(1, 0),
+            _ => panic!(),
+        };
+        let dist: usize = tokens[1].parse().unwrap();
+
+        for _ in 0..dist {
+            knots[0] += dir;
+
+            for i in 0..NUM_KNOTS - 1 {
+                let d = knots[i] - knots[i + 1];
+                if d.abs_chebyshev() > 1 {
+                    knots[i + 1] += d.clamp_chebyshev(1);
+                }
+            }
+
+            for (trail, knot) in trails.iter_mut().zip(knots) {
+                trail.push(knot);
+            }
+            tail_visited.insert(knots[NUM_KNOTS - 1]);
+            steps += 1;
+        }
+    }
+
+    write_svg(
+        &mut BufWriter::new(File::create(SVG_PATH)?),
+        &trails,
+        &tail_visited,
+    )?;
+
+    let vertices: usize = trails.iter().map(|t| t.points.len()).sum();
+    println!(
+        "{} steps, {} tail cells, {} vertices written to {}",
+        steps,
+        tail_visited.len(),
+        vertices,
+        SVG_PATH
+    );
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeSet;
+use std::error;
+use std::io;
+use std::io::Read;
+use std::process;
+
+// The instruction set, assembler and machine are shared with day10_debug
+#[path = "../day10_cpu.rs"]
+mod day10_cpu;
+
+use day10_cpu::assemble;
+use day10_cpu::Arg;
+use day10_cpu::Instr;
+use day10_cpu::Machine;
+use day10_cpu::NUM_REGS;
+use day10_cpu::REG_NAMES;
+
+// Print the disassembled program, annotated with cycle costs, before running it
+const PRINT_LISTING: bool = true;
+
+// Turns a program back into source, naming each jump target after its instruction index
+fn disassemble(program: &[Instr], annotate_cycles: bool) -> String {
+    let targets: BTreeSet<usize> = program
+        .iter()
+        .flat_map(|instr| &instr.args)
+        .filter_map(|arg| match arg {
+            Arg::Target(t) => Some(*t),
+            _ => None,
+        })
+        .collect();
+
+    let mut lines = vec![];
+
+    for i in 0..=program.len() {
+        if targets.contains(&i) {
+            lines.push(format!("l{}:", i));
+        }
+        let Some(instr) = program.get(i) else {
+            break;
+        };
+
+        let info = instr.op.info();
+        let mut line = format!("    {}", info.mnemonic);
+        let args: Vec<_> = instr.args.iter().map(Arg::to_string).collect();
+        if !args.is_empty() {
+            line = format!("{} {}", line, args.join(", "));
+        }
+        if annotate_cycles {
+            line = format!("{:<20}; cycles: {}", line, info.cycles);
+        }
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+fn run() -> Result<(), Box<dyn error::Error>> {
+    let mut source = String::new();
+    io::stdin().read_to_string(&mut source)?;
+
+    let program = assemble(&source)?;
+
+    let listing = disassemble(&program, true);
+    // The listing should assemble back to the same program, but that's only worth a second pass in
+    // debug builds
+    if cfg!(debug_assertions) {
+        assert_eq!(assemble(&listing)?, program);
+    }
+    if PRINT_LISTING {
+        println!("{}\n", listing);
+    }
+
+    let mut m = Machine::new(program);
+
+    while m.step().is_ok() {}
+
+    print!("{}", m.render_disp());
+    println!();
+
+    let regs: Vec<_> = (0..NUM_REGS)
+        .map(|r| format!("{}={}", REG_NAMES[r], m.reg[r]))
+        .collect();
+    println!("cycle {}: {}", m.cycle, regs.join(" "));
+
+    Ok(())
+}
+
+// Returning the error from main would only show its Debug form
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,455 @@
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::process;
+use std::str::FromStr;
+
+// The instruction set, assembler and machine are shared with day10_asm
+#[path = "../day10_cpu.rs"]
+mod day10_cpu;
+
+use day10_cpu::assemble;
+use day10_cpu::parse_reg;
+use day10_cpu::Arg;
+use day10_cpu::Instr;
+use day10_cpu::Machine;
+use day10_cpu::NUM_REGS;
+use day10_cpu::REG_NAMES;
+
+// Source for the program to debug, since stdin is used for debugger commands
+const PROGRAM_PATH: &str = "day10.txt";
+
+fn format_instr(instr: &Instr) -> String {
+    let args: Vec<_> = instr.args.iter().map(Arg::to_string).collect();
+    format!("{} {}", instr.op.info().mnemonic, args.join(", "))
+        .trim_end()
+        .to_owned()
+}
+
+const HELP: &str = "\
+step [n]          run n cycles, 1 by default
+continue          run until a breakpoint or the end of the program
+break cycle N     stop once N cycles have run
+break pc N        stop whenever instruction N is about to start
+break E OP N      stop when a condition becomes true, e.g. `break x > 20`
+delete N          remove breakpoint N
+watch E           print E whenever the machine stops
+unwatch N         remove watch N
+info              list breakpoints and watches
+print E           print the value of E
+regs              print the machine state
+crt               print the display drawn so far
+list              print the program
+quit
+
+E is `cycle`, `pc` or a register name. OP is one of < <= == != >= >.";
+
+// Something to inspect about the machine
+#[derive(Clone, Copy, Debug)]
+enum Expr {
+    Cycle,
+    Pc,
+    Reg(usize),
+}
+
+impl FromStr for Expr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cycle" => Ok(Expr::Cycle),
+            "pc" => Ok(Expr::Pc),
+            _ => parse_reg(s)
+                .map(Expr::Reg)
+                .ok_or_else(|| format!("unknown expression {:?}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Cycle => write!(f, "cycle"),
+            Expr::Pc => write!(f, "pc"),
+            Expr::Reg(r) => write!(f, "{}", REG_NAMES[*r]),
+        }
+    }
+}
+
+impl Expr {
+    fn eval(self, m: &Machine) -> isize {
+        match self {
+            Expr::Cycle => m.cycle as isize,
+            Expr::Pc => m.pc as isize,
+            Expr::Reg(r) => m.reg[r],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum CmpOp {
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    Ge,
+    Gt,
+}
+
+const CMP_OPS: [(&str, CmpOp); 6] = [
+    ("<", CmpOp::Lt),
+    ("<=", CmpOp::Le),
+    ("==", CmpOp::Eq),
+    ("!=", CmpOp::Ne),
+    (">=", CmpOp::Ge),
+    (">", CmpOp::Gt),
+];
+
+impl CmpOp {
+    fn apply(self, a: isize, b: isize) -> bool {
+        match self {
+            CmpOp::Lt => a < b,
+            CmpOp::Le => a <= b,
+            CmpOp::Eq => a == b,
+            CmpOp::Ne => a != b,
+            CmpOp::Ge => a >= b,
+            CmpOp::Gt => a > b,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        CMP_OPS.iter().find(|(_, op)| *op == self).unwrap().0
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Cond {
+    lhs: Expr,
+    op: CmpOp,
+    rhs: isize,
+}
+
+impl Cond {
+    fn holds(&self, m: &Machine) -> bool {
+        self.op.apply(self.lhs.eval(m), self.rhs)
+    }
+}
+
+impl fmt::Display for Cond {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.lhs, self.op.symbol(), self.rhs)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Breakpoint {
+    Cycle(usize),
+    Pc(usize),
+    When(Cond),
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Breakpoint::Cycle(n) => write!(f, "cycle {}", n),
+            Breakpoint::Pc(n) => write!(f, "pc {}", n),
+            Breakpoint::When(cond) => write!(f, "{}", cond),
+        }
+    }
+}
+
+impl Breakpoint {
+    // Whether the machine is at this breakpoint's place, before running the next cycle
+    fn at(&self, m: &Machine) -> bool {
+        match *self {
+            Breakpoint::Cycle(n) => m.cycle == n,
+            Breakpoint::Pc(n) => m.instr_cycle == 0 && m.pc == n,
+            Breakpoint::When(_) => false,
+        }
+    }
+
+    fn cond_holds(&self, m: &Machine) -> bool {
+        match self {
+            Breakpoint::When(cond) => cond.holds(m),
+            _ => false,
+        }
+    }
+}
+
+fn parse_number<T: FromStr>(token: &str) -> Result<T, String> {
+    token
+        .parse()
+        .map_err(|_| format!("expected a number, found {:?}", token))
+}
+
+#[derive(Debug)]
+enum Command {
+    Step { count: usize },
+    Continue,
+    Break { bp: Breakpoint },
+    Delete { index: usize },
+    Watch { expr: Expr },
+    Unwatch { index: usize },
+    Info,
+    Print { expr: Expr },
+    Regs,
+    Crt,
+    List,
+    Help,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_whitespace().collect();
+        let command = match *tokens.as_slice() {
+            ["step" | "s"] => Command::Step { count: 1 },
+            ["step" | "s", n] => Command::Step {
+                count: parse_number(n)?,
+            },
+            ["continue" | "c"] => Command::Continue,
+            ["break" | "b", "cycle", n] | ["break" | "b", "cycle", "==", n] => Command::Break {
+                bp: Breakpoint::Cycle(parse_number(n)?),
+            },
+            ["break" | "b", "pc", n] | ["break" | "b", "pc", "==", n] => Command::Break {
+                bp: Breakpoint::Pc(parse_number(n)?),
+            },
+            ["break" | "b", lhs, op, rhs] => {
+                let (_, op) = CMP_OPS
+                    .iter()
+                    .find(|(symbol, _)| *symbol == op)
+                    .ok_or_else(|| format!("unknown comparison {:?}", op))?;
+                Command::Break {
+                    bp: Breakpoint::When(Cond {
+                        lhs: lhs.parse()?,
+                        op: *op,
+                        rhs: parse_number(rhs)?,
+                    }),
+                }
+            }
+            ["delete" | "d", n] => Command::Delete {
+                index: parse_number(n)?,
+            },
+            ["watch" | "w", expr] => Command::Watch {
+                expr: expr.parse()?,
+            },
+            ["unwatch", n] => Command::Unwatch {
+                index: parse_number(n)?,
+            },
+            ["info" | "i"] => Command::Info,
+            ["print" | "p", expr] => Command::Print {
+                expr: expr.parse()?,
+            },
+            ["regs" | "r"] => Command::Regs,
+            ["crt"] => Command::Crt,
+            ["list" | "l"] => Command::List,
+            ["help" | "h"] => Command::Help,
+            ["quit" | "q"] => Command::Quit,
+            _ => return Err(format!("can't parse {:?}, try `help`", s)),
+        };
+
+        Ok(command)
+    }
+}
+
+struct Debugger {
+    m: Machine,
+    // Removed entries are left empty, so the numbers of the others don't change
+    breakpoints: Vec<Option<Breakpoint>>,
+    watches: Vec<Option<Expr>>,
+    // Cycle at which the last breakpoint stopped the machine, so resuming doesn't stop there again
+    stopped_at: Option<usize>,
+}
+
+impl Debugger {
+    // Runs at most `max_cycles` cycles, stopping early at a breakpoint or the end of the program.
+    // Cycle and pc breakpoints stop the machine before the cycle they name runs, while conditions
+    // stop it once they become true. Returns why it stopped.
+    fn run(&mut self, max_cycles: Option<usize>) -> String {
+        let mut cycles = 0;
+
+        loop {
+            if self.stopped_at != Some(self.m.cycle) {
+                let hit = self
+                    .breakpoints
+                    .iter()
+                    .enumerate()
+                    .find_map(|(i, bp)| bp.filter(|bp| bp.at(&self.m)).map(|bp| (i, bp)));
+                if let Some((i, bp)) = hit {
+                    self.stopped_at = Some(self.m.cycle);
+                    return format!("breakpoint {}: {}", i, bp);
+                }
+            }
+
+            if max_cycles.is_some_and(|max| cycles == max) {
+                return format!("ran {} cycles", cycles);
+            }
+
+            let held: Vec<bool> = self
+                .breakpoints
+                .iter()
+                .map(|bp| bp.is_some_and(|bp| bp.cond_holds(&self.m)))
+                .collect();
+
+            if self.m.step().is_err() {
+                return match self.m.program.get(self.m.pc) {
+                    Some(_) => format!("display full after {} cycles", self.m.cycle),
+                    None => format!("program finished after {} cycles", self.m.cycle),
+                };
+            }
+            cycles += 1;
+
+            let hit = self
+                .breakpoints
+                .iter()
+                .zip(held)
+                .enumerate()
+                .find(|(_, (bp, held))| !held && bp.is_some_and(|bp| bp.cond_holds(&self.m)));
+            if let Some((i, (bp, _))) = hit {
+                self.stopped_at = Some(self.m.cycle);
+                return format!("breakpoint {}: {}", i, bp.unwrap());
+            }
+        }
+    }
+
+    fn location(&self) -> String {
+        match self.m.program.get(self.m.pc) {
+            Some(instr) => format!(
+                "cycle {}, pc {}: {} ({}/{} cycles done)",
+                self.m.cycle,
+                self.m.pc,
+                format_instr(instr),
+                self.m.instr_cycle,
+                instr.cycles()
+            ),
+            None => format!("cycle {}, pc {}: end of program", self.m.cycle, self.m.pc),
+        }
+    }
+
+    fn print_watches(&self) {
+        for (i, expr) in self.watches.iter().enumerate() {
+            if let Some(expr) = expr {
+                println!("  {}: {} = {}", i, expr, expr.eval(&self.m));
+            }
+        }
+    }
+
+    // Returns false once the user asks to quit
+    fn execute(&mut self, command: Command) -> bool {
+        match command {
+            Command::Step { count } => {
+                println!("{}", self.run(Some(count)));
+                println!("{}", self.location());
+                self.print_watches();
+            }
+            Command::Continue => {
+                println!("{}", self.run(None));
+                println!("{}", self.location());
+                self.print_watches();
+            }
+            Command::Break { bp } => {
+                println!("breakpoint {}: {}", self.breakpoints.len(), bp);
+                self.breakpoints.push(Some(bp));
+            }
+            Command::Delete { index } => match self.breakpoints.get_mut(index) {
+                Some(bp @ Some(_)) => *bp = None,
+                _ => println!("no breakpoint {}", index),
+            },
+            Command::Watch { expr } => {
+                println!("watch {}: {}", self.watches.len(), expr);
+                self.watches.push(Some(expr));
+            }
+            Command::Unwatch { index } => match self.watches.get_mut(index) {
+                Some(watch @ Some(_)) => *watch = None,
+                _ => println!("no watch {}", index),
+            },
+            Command::Info => {
+                for (i, bp) in self.breakpoints.iter().enumerate() {
+                    if let Some(bp) = bp {
+                        println!("breakpoint {}: {}", i, bp);
+                    }
+                }
+                for (i, expr) in self.watches.iter().enumerate() {
+                    if let Some(expr) = expr {
+                        println!("watch {}: {}", i, expr);
+                    }
+                }
+            }
+            Command::Print { expr } => println!("{} = {}", expr, expr.eval(&self.m)),
+            Command::Regs => {
+                println!("{}", self.location());
+                let regs: Vec<_> = (0..NUM_REGS)
+                    .map(|r| format!("{}={}", REG_NAMES[r], self.m.reg[r]))
+                    .collect();
+                println!("{}", regs.join(" "));
+            }
+            Command::Crt => print!("{}", self.m.render_disp()),
+            Command::List => {
+                for (i, instr) in self.m.program.iter().enumerate() {
+                    let marker = if i == self.m.pc { "=>" } else { "  " };
+                    println!("{} l{}: {}", marker, i, format_instr(instr));
+                }
+            }
+            Command::Help => println!("{}", HELP),
+            Command::Quit => return false,
+        }
+
+        true
+    }
+}
+
+fn run() -> Result<(), Box<dyn error::Error>> {
+    let source = fs::read_to_string(PROGRAM_PATH)?;
+    let program = assemble(&source)?;
+
+    println!(
+        "loaded {} instructions from {}, type `help` for commands",
+        program.len(),
+        PROGRAM_PATH
+    );
+
+    let mut debugger = Debugger {
+        m: Machine::new(program),
+        breakpoints: vec![],
+        watches: vec![],
+        stopped_at: None,
+    };
+
+    let mut lines = io::stdin().lines();
+    loop {
+        print!("(dbg) ");
+        io::stdout().flush()?;
+
+        let Some(line) = lines.next() else {
+            println!();
+            break;
+        };
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match line.parse() {
+            Ok(command) => {
+                if !debugger.execute(command) {
+                    break;
+                }
+            }
+            Err(message) => println!("{}", message),
+        }
+    }
+
+    Ok(())
+}
+
+// Returning the error from main would only show its Debug form
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
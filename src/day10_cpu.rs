@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::ops;
+
+const DISP_WIDTH: usize = 40;
+const DISP_HEIGHT: usize = 6;
+
+// The sprite position is always the first register
+pub const REG_NAMES: [&str; 4] = ["x", "y", "z", "w"];
+pub const NUM_REGS: usize = REG_NAMES.len();
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Opcode {
+    NoOp,
+    AddX,
+    Set,
+    Add,
+    Mul,
+    Jmp,
+    Jz,
+    Jnz,
+    Halt,
+}
+
+// What each operand of an instruction may be
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    Reg,
+    // A register or an immediate
+    Value,
+    Label,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Kind::Reg => "a register",
+            Kind::Value => "a register or number",
+            Kind::Label => "a label",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug)]
+pub struct OpInfo {
+    op: Opcode,
+    pub mnemonic: &'static str,
+    pub cycles: usize,
+    operands: &'static [Kind],
+}
+
+// The whole instruction set. Adding an instruction means adding it here and to Machine::execute.
+const ISA: &[OpInfo] = &[
+    OpInfo {
+        op: Opcode::NoOp,
+        mnemonic: "noop",
+        cycles: 1,
+        operands: &[],
+    },
+    OpInfo {
+        op: Opcode::AddX,
+        mnemonic: "addx",
+        cycles: 2,
+        operands: &[Kind::Value],
+    },
+    OpInfo {
+        op: Opcode::Set,
+        mnemonic: "set",
+        cycles: 1,
+        operands: &[Kind::Reg, Kind::Value],
+    },
+    OpInfo {
+        op: Opcode::Add,
+        mnemonic: "add",
+        cycles: 2,
+        operands: &[Kind::Reg, Kind::Value],
+    },
+    OpInfo {
+        op: Opcode::Mul,
+        mnemonic: "mul",
+        cycles: 3,
+        operands: &[Kind::Reg, Kind::Value],
+    },
+    OpInfo {
+        op: Opcode::Jmp,
+        mnemonic: "jmp",
+        cycles: 1,
+        operands: &[Kind::Label],
+    },
+    OpInfo {
+        op: Opcode::Jz,
+        mnemonic: "jz",
+        cycles: 2,
+        operands: &[Kind::Reg, Kind::Label],
+    },
+    OpInfo {
+        op: Opcode::Jnz,
+        mnemonic: "jnz",
+        cycles: 2,
+        operands: &[Kind::Reg, Kind::Label],
+    },
+    OpInfo {
+        op: Opcode::Halt,
+        mnemonic: "halt",
+        cycles: 1,
+        operands: &[],
+    },
+];
+
+impl Opcode {
+    pub fn info(self) -> &'static OpInfo {
+        ISA.iter().find(|info| info.op == self).unwrap()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Arg {
+    Reg(usize),
+    Imm(isize),
+    // Index of the instruction to jump to
+    Target(usize),
+}
+
+// Jump targets are shown as labels named after their instruction index
+impl fmt::Display for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arg::Reg(r) => write!(f, "{}", REG_NAMES[*r]),
+            Arg::Imm(v) => write!(f, "{}", v),
+            Arg::Target(t) => write!(f, "l{}", t),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Instr {
+    pub op: Opcode,
+    pub args: Vec<Arg>,
+}
+
+impl Instr {
+    pub fn cycles(&self) -> usize {
+        self.op.info().cycles
+    }
+}
+
+#[derive(Debug)]
+pub struct AsmError {
+    line: usize,
+    message: String,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl error::Error for AsmError {}
+
+pub fn parse_reg(token: &str) -> Option<usize> {
+    REG_NAMES.iter().position(|name| *name == token)
+}
+
+// Assembles one instruction per line. Lines may start with `label:` and anything after `;` is a
+// comment.
+pub fn assemble(source: &str) -> Result<Vec<Instr>, AsmError> {
+    // First pass: split off labels and find the instruction each one points at
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut lines: Vec<(usize, Vec<&str>)> = vec![];
+
+    for (line, text) in (1..).zip(source.lines()) {
+        let code = text.split(';').next().unwrap();
+        let mut tokens: Vec<_> = code
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        while let Some(label) = tokens.first().and_then(|t| t.strip_suffix(':')) {
+            let is_ident = label.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !is_ident {
+                let message = format!("invalid label {:?}", label);
+                return Err(AsmError { line, message });
+            }
+            if labels.insert(label, lines.len()).is_some() {
+                let message = format!("label {:?} defined twice", label);
+                return Err(AsmError { line, message });
+            }
+            tokens.remove(0);
+        }
+
+        if !tokens.is_empty() {
+            lines.push((line, tokens));
+        }
+    }
+
+    // Second pass: parse instructions, now that every label is known
+    let mut program = vec![];
+
+    for (line, tokens) in lines {
+        let error = |message: String| AsmError { line, message };
+
+        let (mnemonic, operands) = tokens.split_first().unwrap();
+        let info = ISA
+            .iter()
+            .find(|info| info.mnemonic == *mnemonic)
+            .ok_or_else(|| error(format!("unknown instruction {:?}", mnemonic)))?;
+
+        if operands.len() != info.operands.len() {
+            return Err(error(format!(
+                "{} takes {} operands, found {}",
+                info.mnemonic,
+                info.operands.len(),
+                operands.len()
+            )));
+        }
+
+        let args = info
+            .operands
+            .iter()
+            .zip(operands)
+            .map(|(kind, token)| {
+                let arg = match kind {
+                    Kind::Reg => parse_reg(token).map(Arg::Reg),
+                    Kind::Value => parse_reg(token)
+                        .map(Arg::Reg)
+                        .or_else(|| token.parse().ok().map(Arg::Imm)),
+                    Kind::Label => labels.get(token).copied().map(Arg::Target),
+                };
+                arg.ok_or_else(|| error(format!("expected {}, found {:?}", kind, token)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        program.push(Instr { op: info.op, args });
+    }
+
+    Ok(program)
+}
+
+#[derive(Debug)]
+pub struct Registers([isize; NUM_REGS]);
+
+impl ops::Index<usize> for Registers {
+    type Output = isize;
+    fn index(&self, r: usize) -> &Self::Output {
+        &self.0[r]
+    }
+}
+
+impl ops::IndexMut<usize> for Registers {
+    fn index_mut(&mut self, r: usize) -> &mut Self::Output {
+        &mut self.0[r]
+    }
+}
+
+#[derive(Debug)]
+pub struct Machine {
+    pub program: Vec<Instr>,
+    pub pc: usize,
+    pub cycle: usize,
+    pub instr_cycle: usize,
+    pub reg: Registers,
+    disp: [[u8; DISP_WIDTH]; DISP_HEIGHT],
+}
+
+impl Machine {
+    pub fn new(program: Vec<Instr>) -> Self {
+        let mut reg = Registers([0; NUM_REGS]);
+        reg[0] = 1;
+
+        Machine {
+            program,
+            pc: 0,
+            cycle: 0,
+            instr_cycle: 0,
+            reg,
+            disp: [[b'?'; DISP_WIDTH]; DISP_HEIGHT],
+        }
+    }
+
+    fn value(&self, arg: Arg) -> isize {
+        match arg {
+            Arg::Reg(r) => self.reg[r],
+            Arg::Imm(v) => v,
+            Arg::Target(_) => panic!("{:?} isn't a value", arg),
+        }
+    }
+
+    // Runs an instruction's effect once its last cycle is over, returning where to go next
+    fn execute(&mut self, instr: &Instr) -> usize {
+        let next = self.pc + 1;
+        match (instr.op, instr.args.as_slice()) {
+            (Opcode::NoOp, []) => next,
+            (Opcode::AddX, &[v]) => {
+                self.reg[0] += self.value(v);
+                next
+            }
+            (Opcode::Set, &[Arg::Reg(r), v]) => {
+                self.reg[r] = self.value(v);
+                next
+            }
+            (Opcode::Add, &[Arg::Reg(r), v]) => {
+                self.reg[r] += self.value(v);
+                next
+            }
+            (Opcode::Mul, &[Arg::Reg(r), v]) => {
+                self.reg[r] *= self.value(v);
+                next
+            }
+            (Opcode::Jmp, &[Arg::Target(t)]) => t,
+            (Opcode::Jz, &[Arg::Reg(r), Arg::Target(t)]) => {
+                if self.reg[r] == 0 {
+                    t
+                } else {
+                    next
+                }
+            }
+            (Opcode::Jnz, &[Arg::Reg(r), Arg::Target(t)]) => {
+                if self.reg[r] != 0 {
+                    t
+                } else {
+                    next
+                }
+            }
+            (Opcode::Halt, []) => self.program.len(),
+            _ => panic!("malformed instruction {:?}", instr),
+        }
+    }
+
+    // Fails once the program has finished or the display is full
+    pub fn step(&mut self) -> Result<(), ()> {
+        if self.cycle == DISP_WIDTH * DISP_HEIGHT {
+            return Err(());
+        }
+        let current_instr = self.program.get(self.pc).ok_or(())?.clone();
+
+        // Update display
+        let disp_x = self.cycle % DISP_WIDTH;
+        let disp_y = self.cycle / DISP_WIDTH;
+        self.disp[disp_y][disp_x] = if self.reg[0].abs_diff(disp_x as isize) <= 1 {
+            b'#'
+        } else {
+            b'.'
+        };
+
+        // Execute instruction
+        self.cycle += 1;
+        self.instr_cycle += 1;
+        if self.instr_cycle == current_instr.cycles() {
+            self.pc = self.execute(&current_instr);
+            self.instr_cycle = 0;
+        }
+
+        Ok(())
+    }
+
+    pub fn render_disp(&self) -> String {
+        let mut vec: Vec<u8> = vec![];
+        for row in self.disp {
+            vec.extend_from_slice(&row);
+            vec.push(b'\n');
+        }
+        String::from_utf8(vec).unwrap()
+    }
+}